@@ -6,16 +6,80 @@ use crate::error::{Error, ErrorKind};
 
 pub enum StructuredAttributeArguments {
     Identifier(String),
+    Assignment(String, Box<syn::Expr>),
+    /// An attribute like `unique(movie_id, genre_id)`.
+    List(String, Vec<syn::Ident>),
 }
 impl StructuredAttributeArguments {
     fn new(argument: syn::Expr) -> Option<Self> {
         match argument {
             syn::Expr::Path(path) => Some(Self::Identifier(path.path.get_ident()?.to_string())),
+            syn::Expr::Assign(assign) => {
+                let syn::Expr::Path(path) = *assign.left else {
+                    return None;
+                };
+                Some(Self::Assignment(
+                    path.path.get_ident()?.to_string(),
+                    assign.right,
+                ))
+            }
+            syn::Expr::Call(call) => {
+                let syn::Expr::Path(path) = *call.func else {
+                    return None;
+                };
+                let arguments = call
+                    .args
+                    .into_iter()
+                    .map(|a| match a {
+                        syn::Expr::Path(path) => path.path.get_ident().cloned(),
+                        _ => None,
+                    })
+                    .collect::<Option<_>>()?;
+                Some(Self::List(path.path.get_ident()?.to_string(), arguments))
+            }
             _ => None,
         }
     }
 }
 
+/// A literal given with `#[silo(default = <lit>)]`.
+#[derive(Debug, Clone)]
+pub struct DefaultValue {
+    /// The literal as rust expression, strings are converted with `into`.
+    pub expr: proc_macro2::TokenStream,
+    /// The literal as sql, used in the column definition.
+    pub sql: String,
+}
+
+impl DefaultValue {
+    fn new(expr: &syn::Expr) -> Option<Self> {
+        let (is_negative, lit) = match expr {
+            syn::Expr::Lit(lit) => (false, &lit.lit),
+            syn::Expr::Unary(syn::ExprUnary {
+                op: syn::UnOp::Neg(_),
+                expr,
+                ..
+            }) => match &**expr {
+                syn::Expr::Lit(lit) => (true, &lit.lit),
+                _ => return None,
+            },
+            _ => return None,
+        };
+        let sign = if is_negative { "-" } else { "" };
+        let (expr, sql) = match lit {
+            syn::Lit::Str(it) if !is_negative => (
+                quote!(#it.into()),
+                format!("'{}'", it.value().replace('\'', "''")),
+            ),
+            syn::Lit::Int(it) => (quote!(#expr), format!("{sign}{}", it.base10_digits())),
+            syn::Lit::Float(it) => (quote!(#expr), format!("{sign}{}", it.base10_digits())),
+            syn::Lit::Bool(it) if !is_negative => (quote!(#it), (it.value as u8).to_string()),
+            _ => return None,
+        };
+        Some(Self { expr, sql })
+    }
+}
+
 pub struct StructuredAttribute {
     span: Span,
     path: String,
@@ -42,12 +106,23 @@ pub struct ToTableAttributesStruct {
     pub on_conflict_ignore: bool,
     pub on_conflict_replace: bool,
     pub has_custom_migration_handler: bool,
+    pub is_strict: bool,
+    pub generate_insert_struct: bool,
+    pub is_soft_delete: bool,
+    /// The fields of every `#[silo(unique(..))]`.
+    pub unique_constraints: Vec<Vec<syn::Ident>>,
+    /// The fields of `#[silo(replace(..))]`, whose conflicts are replaced.
+    pub replace_conflict_fields: Vec<syn::Ident>,
 }
 
 impl ToTableAttributesStruct {
     pub fn parse(attrs: &[Attribute]) -> Result<ToTableAttributesStruct, Error> {
         let mut this = Self::default();
         for attribute in attrs {
+            // Doc comments are forwarded onto the generated types instead.
+            if attribute.path().is_ident("doc") {
+                continue;
+            }
             let Some(attribute) = StructuredAttribute::new(attribute) else {
                 panic!("Invalid attribute");
             };
@@ -65,10 +140,33 @@ impl ToTableAttributesStruct {
                     "ignore" => this.on_conflict_ignore = true,
                     "replace" => this.on_conflict_replace = true,
                     "migrate" => this.has_custom_migration_handler = true,
+                    "strict" => this.is_strict = true,
+                    "generate_insert_struct" => this.generate_insert_struct = true,
+                    "soft_delete" => this.is_soft_delete = true,
                     _ => {
                         panic!("Invalid attribute");
                     }
                 },
+                StructuredAttributeArguments::List(name, fields) if name == "unique" => {
+                    this.unique_constraints.push(fields);
+                }
+                StructuredAttributeArguments::List(name, fields) if name == "replace" => {
+                    if !this.replace_conflict_fields.is_empty() {
+                        return Err(Error::new(
+                            attribute.span,
+                            ErrorKind::MultipleConflictAttributes,
+                        ));
+                    }
+                    this.on_conflict_replace = true;
+                    this.replace_conflict_fields = fields;
+                }
+                StructuredAttributeArguments::Assignment(name, _)
+                | StructuredAttributeArguments::List(name, _) => {
+                    return Err(Error::new(
+                        attribute.span,
+                        ErrorKind::InvalidAttribute(name),
+                    ));
+                }
             }
         }
 
@@ -94,17 +192,70 @@ impl ToTableAttributesStruct {
     }
 }
 
+#[derive(Debug, Default)]
+pub struct ToColumnsAttributesStruct {
+    pub is_transparent: bool,
+    pub is_repr_int: bool,
+}
+
+impl ToColumnsAttributesStruct {
+    pub fn parse(attrs: &[Attribute]) -> Result<ToColumnsAttributesStruct, Error> {
+        let mut this = Self::default();
+        for attribute in attrs.iter().filter(|a| a.path().is_ident("silo")) {
+            let Some(attribute) = StructuredAttribute::new(attribute) else {
+                panic!("Invalid attribute");
+            };
+            match attribute.arguments {
+                StructuredAttributeArguments::Identifier(name) => match name.as_str() {
+                    "transparent" => this.is_transparent = true,
+                    "repr_int" => this.is_repr_int = true,
+                    _ => {
+                        return Err(Error::new(
+                            attribute.span,
+                            ErrorKind::InvalidAttribute(name),
+                        ));
+                    }
+                },
+                StructuredAttributeArguments::Assignment(name, _)
+                | StructuredAttributeArguments::List(name, _) => {
+                    return Err(Error::new(
+                        attribute.span,
+                        ErrorKind::InvalidAttribute(name),
+                    ));
+                }
+            }
+        }
+
+        Ok(this)
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct AttributeFieldData {
     pub is_primary: bool,
     pub is_unique: bool,
     pub is_skip: bool,
+    pub is_skip_insert: bool,
+    pub is_autoincrement: bool,
+    pub is_skip_select: bool,
+    pub is_index: bool,
+    pub is_unique_index: bool,
+    pub is_nocase: bool,
+    pub is_json: bool,
+    pub default: Option<DefaultValue>,
+    pub references: Option<syn::Path>,
+    /// The sql condition of `#[silo(check = "..")]`.
+    pub check: Option<syn::LitStr>,
 }
 
 impl AttributeFieldData {
     pub fn parse(attrs: &[Attribute]) -> AttributeFieldData {
         let mut this = Self::default();
         for attribute in attrs {
+            // Doc comments are forwarded onto the generated types instead.
+            if attribute.path().is_ident("doc") {
+                continue;
+            }
             let Some(attribute) = StructuredAttribute::new(attribute) else {
                 panic!("Invalid attribute");
             };
@@ -116,12 +267,55 @@ impl AttributeFieldData {
                     "primary" => this.is_primary = true,
                     "unique" => this.is_unique = true,
                     "skip" => this.is_skip = true,
+                    "skip_insert" => this.is_skip_insert = true,
+                    // SQLite assigns the value, so it is never inserted.
+                    "autoincrement" => {
+                        this.is_autoincrement = true;
+                        this.is_skip_insert = true;
+                    }
+                    "skip_select" => this.is_skip_select = true,
+                    "index" => this.is_index = true,
+                    "unique_index" => this.is_unique_index = true,
+                    "nocase" => this.is_nocase = true,
+                    "json" => this.is_json = true,
                     _ => {
                         panic!("Invalid attribute");
                     }
                 },
+                StructuredAttributeArguments::Assignment(name, value) => match name.as_str() {
+                    "default" => {
+                        this.default =
+                            Some(DefaultValue::new(&value).expect("Default has to be a literal"))
+                    }
+                    "references" => match *value {
+                        syn::Expr::Path(path) => this.references = Some(path.path),
+                        _ => panic!("References has to be the type of a table"),
+                    },
+                    "check" => match *value {
+                        syn::Expr::Lit(syn::ExprLit {
+                            lit: syn::Lit::Str(check),
+                            ..
+                        }) => this.check = Some(check),
+                        _ => panic!("Check has to be a string of sql"),
+                    },
+                    _ => {
+                        panic!("Invalid attribute");
+                    }
+                },
+                StructuredAttributeArguments::List(..) => {
+                    panic!("Invalid attribute");
+                }
             }
         }
         this
     }
 }
+
+/// The doc comments among `attrs`, which are forwarded onto generated types.
+pub(crate) fn doc_attributes(attrs: &[Attribute]) -> Vec<Attribute> {
+    attrs
+        .iter()
+        .filter(|a| a.path().is_ident("doc"))
+        .cloned()
+        .collect()
+}
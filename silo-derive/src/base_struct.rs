@@ -1,4 +1,4 @@
-use crate::attributes::AttributeFieldData;
+use crate::attributes::{AttributeFieldData, DefaultValue};
 use crate::error::Error;
 use proc_macro2::{Span, TokenStream};
 use quote::{ToTokens, format_ident, quote};
@@ -7,13 +7,52 @@ use syn::{Ident, Type, Visibility, spanned::Spanned};
 #[derive(Clone, Copy)]
 pub struct Field<'a> {
     pub name: &'a Ident,
+    /// The type used for storage, for `#[silo(json)]` fields this is
+    /// `silo::json::Json<T>` instead of the declared type.
     pub type_: &'a Type,
+    pub is_json: bool,
+    pub default: Option<&'a DefaultValue>,
+    pub is_skip_select: bool,
+    /// The doc comments of the declared field.
+    pub docs: &'a [syn::Attribute],
 }
 impl Field<'_> {
     pub(crate) fn map_type(self, f: impl Fn(&Type) -> &Type) -> Self {
         Self {
             name: self.name,
             type_: f(self.type_),
+            is_json: self.is_json,
+            default: self.default,
+            is_skip_select: self.is_skip_select,
+            docs: self.docs,
+        }
+    }
+
+    /// The doc comments of the declared field, or `fallback` if it has none.
+    pub(crate) fn docs_or(&self, fallback: &str) -> TokenStream {
+        if self.docs.is_empty() {
+            quote!(#[doc = #fallback])
+        } else {
+            let docs = self.docs;
+            quote!(#(#docs)*)
+        }
+    }
+
+    /// Converts a value of the declared type into the type used for storage.
+    pub(crate) fn wrap_storage(&self, value: TokenStream) -> TokenStream {
+        if self.is_json {
+            quote!(silo::json::Json(#value))
+        } else {
+            value
+        }
+    }
+
+    /// Converts a value of the type used for storage into the declared type.
+    pub(crate) fn unwrap_storage(&self, value: TokenStream) -> TokenStream {
+        if self.is_json {
+            quote!(#value.0)
+        } else {
+            value
         }
     }
 }
@@ -34,9 +73,20 @@ pub struct Member {
     type_: Type,
     is_primary: bool,
     is_unique: bool,
+    is_indexed: bool,
+    is_unique_indexed: bool,
+    is_nocase: bool,
+    is_json: bool,
+    default: Option<DefaultValue>,
+    references: Option<syn::Path>,
+    check: Option<syn::LitStr>,
     is_skipped: bool,
+    is_skip_insert: bool,
+    is_autoincrement: bool,
+    is_skip_select: bool,
     is_remaining_element: bool,
     is_unnamed: bool,
+    docs: Vec<syn::Attribute>,
 }
 
 impl std::fmt::Debug for Member {
@@ -46,13 +96,23 @@ impl std::fmt::Debug for Member {
             .field("name", &self.name)
             .field("is_primary", &self.is_primary)
             .field("is_unique", &self.is_unique)
+            .field("is_indexed", &self.is_indexed)
+            .field("is_unique_indexed", &self.is_unique_indexed)
+            .field("is_nocase", &self.is_nocase)
+            .field("is_json", &self.is_json)
+            .field("default", &self.default)
+            .field("references", &self.references)
+            .field("check", &self.check)
             .field("is_skipped", &self.is_skipped)
+            .field("is_skip_insert", &self.is_skip_insert)
+            .field("is_autoincrement", &self.is_autoincrement)
+            .field("is_skip_select", &self.is_skip_select)
             .field("is_unnamed", &self.is_unnamed)
             .finish()
     }
 }
 impl Member {
-    fn to_partial(self) -> Self {
+    fn into_partial(self) -> Self {
         let t = self.type_;
         Member {
             variant: self.variant,
@@ -61,9 +121,21 @@ impl Member {
             type_: syn::parse_quote!(<#t as silo::partial::HasPartial>::Partial),
             is_primary: self.is_primary,
             is_unique: self.is_unique,
+            is_indexed: self.is_indexed,
+            is_unique_indexed: self.is_unique_indexed,
+            is_nocase: self.is_nocase,
+            // The partial type already uses the storage type.
+            is_json: false,
+            default: self.default,
+            references: self.references,
+            check: self.check,
             is_skipped: self.is_skipped,
+            is_skip_insert: self.is_skip_insert,
+            is_autoincrement: self.is_autoincrement,
+            is_skip_select: self.is_skip_select,
             is_remaining_element: self.is_remaining_element,
             is_unnamed: self.is_unnamed,
+            docs: self.docs,
         }
     }
 
@@ -78,16 +150,32 @@ impl Member {
             .clone()
             .map(|i| (i, false))
             .unwrap_or_else(|| Self::generate_field_name(index, f));
+        let type_ = &f.ty;
         Self {
             variant: None,
             name,
             visibility: f.vis.clone(),
-            type_: f.ty.clone(),
+            type_: if a.is_json {
+                syn::parse_quote!(silo::json::Json<#type_>)
+            } else {
+                type_.clone()
+            },
             is_primary: a.is_primary,
             is_unique: a.is_unique,
+            is_indexed: a.is_index,
+            is_unique_indexed: a.is_unique_index,
+            is_nocase: a.is_nocase,
+            is_json: a.is_json,
+            default: a.default.clone(),
+            references: a.references.clone(),
+            check: a.check.clone(),
             is_skipped: a.is_skip,
+            is_skip_insert: a.is_skip_insert,
+            is_autoincrement: a.is_autoincrement,
+            is_skip_select: a.is_skip_select,
             is_remaining_element: false,
             is_unnamed: name_is_generated,
+            docs: crate::attributes::doc_attributes(&f.attrs),
         }
     }
 
@@ -102,9 +190,20 @@ impl Member {
             type_,
             is_primary: false,
             is_unique: false,
+            is_indexed: false,
+            is_unique_indexed: false,
+            is_nocase: false,
+            is_json: false,
+            default: None,
+            references: None,
+            check: None,
             is_skipped: false,
+            is_skip_insert: false,
+            is_autoincrement: false,
+            is_skip_select: false,
             is_remaining_element: false,
             is_unnamed: false,
+            docs: Vec::new(),
         }
     }
 
@@ -112,6 +211,10 @@ impl Member {
         Field {
             name: &self.name,
             type_: &self.type_,
+            is_json: self.is_json,
+            default: self.default.as_ref(),
+            is_skip_select: self.is_skip_select,
+            docs: &self.docs,
         }
     }
 
@@ -122,6 +225,16 @@ impl Member {
             type_: &self.type_,
             is_unique: self.is_unique,
             is_primary: self.is_primary,
+            is_indexed: self.is_indexed,
+            is_unique_indexed: self.is_unique_indexed,
+            is_nocase: self.is_nocase,
+            is_json: self.is_json,
+            default: self.default.as_ref(),
+            references: self.references.as_ref(),
+            check: self.check.as_ref(),
+            is_skip_insert: self.is_skip_insert,
+            is_autoincrement: self.is_autoincrement,
+            is_skip_select: self.is_skip_select,
         }
     }
 }
@@ -133,6 +246,16 @@ pub struct ColumnData<'a> {
     pub type_: &'a Type,
     pub is_unique: bool,
     pub is_primary: bool,
+    pub is_indexed: bool,
+    pub is_unique_indexed: bool,
+    pub is_nocase: bool,
+    pub is_json: bool,
+    pub default: Option<&'a DefaultValue>,
+    pub references: Option<&'a syn::Path>,
+    pub check: Option<&'a syn::LitStr>,
+    pub is_skip_insert: bool,
+    pub is_autoincrement: bool,
+    pub is_skip_select: bool,
 }
 impl ColumnData<'_> {
     pub(crate) fn ident(&self) -> syn::Ident {
@@ -144,7 +267,6 @@ impl ColumnData<'_> {
 pub struct VariantField {
     pub index: usize,
     pub name: Option<Ident>,
-    pub type_: Type,
     pub span: Span,
 }
 
@@ -177,7 +299,6 @@ impl VariantData {
                 .map(|(i, f)| VariantField {
                     index: i + index_offset,
                     name: f.ident.clone(),
-                    type_: f.ty.clone(),
                     span: f.span(),
                 })
                 .collect(),
@@ -209,6 +330,11 @@ pub struct StructData {
     pub is_partial: bool,
     pub is_row_type: bool,
     pub(crate) original_name: Ident,
+    /// The doc comments of the declared type.
+    docs: Vec<syn::Attribute>,
+    /// The generic parameters of the declared type, shared by every generated
+    /// type. See [`Self::with_generics`] for its where clause.
+    pub(crate) generics: syn::Generics,
 }
 
 impl StructData {
@@ -224,7 +350,12 @@ impl StructData {
         format_ident!("Partial{}", self.name)
     }
 
+    pub(crate) fn insertable_name(&self) -> Ident {
+        format_ident!("New{}", self.name)
+    }
+
     pub(crate) fn from_struct_data(
+        attrs: &[syn::Attribute],
         visibility: Visibility,
         name: Ident,
         fields: syn::Fields,
@@ -244,10 +375,14 @@ impl StructData {
             variants: Vec::new(),
             is_partial: false,
             is_row_type: false,
+            docs: crate::attributes::doc_attributes(attrs),
+            generics: syn::Generics::default(),
         };
         if fields.iter().find(|f| !f.0.is_skip).is_none() {
             return Err(Error::new(name_span, crate::error::ErrorKind::NoColumns));
         }
+        check_nested_vecs(&fields)?;
+        check_skip_inserts(&fields)?;
         if let Some(multiple_primaries) = fields.iter().filter(|f| f.0.is_primary).nth(1) {
             return Err(Error::new(
                 // TODO: I would like ident.span() more, but if it is a tuple
@@ -261,6 +396,7 @@ impl StructData {
     }
 
     pub(crate) fn from_enum_data(
+        attrs: &[syn::Attribute],
         visibility: Visibility,
         name: Ident,
         variants: syn::punctuated::Punctuated<syn::Variant, syn::token::Comma>,
@@ -269,7 +405,9 @@ impl StructData {
             .iter()
             .flat_map(|v| v.fields.iter())
             .map(|f| (AttributeFieldData::parse(&f.attrs), f.clone()))
-            .collect();
+            .collect::<Vec<_>>();
+        check_nested_vecs(&fields)?;
+        check_skip_inserts(&fields)?;
         let mut index_offset = 0;
         let variants: Vec<_> = variants
             .iter()
@@ -289,11 +427,54 @@ impl StructData {
             skipped_members: vec![],
             is_partial: false,
             is_row_type: false,
+            docs: crate::attributes::doc_attributes(attrs),
+            generics: syn::Generics::default(),
         };
         this.populate_members(fields);
         Ok(this)
     }
 
+    /// Makes the generated types and impls generic like the declared type. If
+    /// it has type parameters, they only work for field types, which can be
+    /// stored in columns, so the where clause requires that of every field
+    /// type. Skipped fields are read as their default.
+    pub(crate) fn with_generics(mut self, mut generics: syn::Generics) -> Self {
+        if generics.type_params().next().is_some() {
+            let where_clause = generics.make_where_clause();
+            for type_ in self.members.iter().map(|m| &m.type_) {
+                where_clause.predicates.push(syn::parse_quote! {
+                    #type_: silo::AsColumns
+                        + silo::AsParams
+                        + silo::ExtractFromRow
+                        + silo::filter::Filterable
+                        + silo::partial::HasPartial
+                });
+                where_clause.predicates.push(syn::parse_quote! {
+                    <#type_ as silo::partial::HasPartial>::Partial: silo::partial::PartialType<#type_>
+                        + silo::AsColumnsOptional
+                        + silo::AsParamsOptional
+                        + silo::ExtractFromRow
+                });
+                where_clause.predicates.push(syn::parse_quote! {
+                    <#type_ as silo::filter::Filterable>::Filter: Default
+                });
+            }
+            for type_ in self
+                .members
+                .iter()
+                .filter(|m| m.is_skip_select)
+                .chain(&self.skipped_members)
+                .map(|m| &m.type_)
+            {
+                where_clause
+                    .predicates
+                    .push(syn::parse_quote!(#type_: Default));
+            }
+        }
+        self.generics = generics;
+        self
+    }
+
     fn populate_members(&mut self, fields: Vec<(AttributeFieldData, syn::Field)>) {
         self.skipped_members = fields
             .iter()
@@ -333,18 +514,47 @@ impl StructData {
                 .members
                 .iter()
                 .cloned()
-                .map(Member::to_partial)
+                .map(Member::into_partial)
                 .collect(),
             skipped_members: self
                 .skipped_members
                 .iter()
                 .cloned()
-                .map(Member::to_partial)
+                .map(Member::into_partial)
                 .collect(),
-            variant_member: self.variant_member.clone().map(Member::to_partial),
+            variant_member: self.variant_member.clone().map(Member::into_partial),
             variants: self.variants.clone(),
             is_row_type: self.is_row_type,
             is_partial: true,
+            docs: self.docs.clone(),
+            generics: self.generics.clone(),
+        }
+    }
+
+    /// The struct with only the fields, which are inserted and not the
+    /// primary key. Like the partial struct, it uses the storage types.
+    pub(crate) fn to_insertable(&self) -> StructData {
+        StructData {
+            visibility: self.visibility.clone(),
+            original_name: self.original_name.clone(),
+            name: self.insertable_name(),
+            members: self
+                .members
+                .iter()
+                .filter(|m| !m.is_primary && !m.is_skip_insert)
+                .cloned()
+                .map(|m| Member {
+                    is_json: false,
+                    ..m
+                })
+                .collect(),
+            skipped_members: Vec::new(),
+            variant_member: None,
+            variants: Vec::new(),
+            is_row_type: false,
+            is_partial: false,
+            docs: self.docs.clone(),
+            generics: self.generics.clone(),
         }
     }
 
@@ -362,6 +572,93 @@ impl StructData {
             .find(|m| m.is_primary)
             .map(|m| m.to_field())
     }
+
+    pub(crate) fn has_member(&self, name: &Ident) -> bool {
+        self.members.iter().any(|m| &m.name == name)
+    }
+
+    /// Whether the field is the primary key or marked `#[silo(unique)]`.
+    pub(crate) fn is_unique_member(&self, name: &Ident) -> bool {
+        self.members
+            .iter()
+            .any(|m| &m.name == name && (m.is_primary || m.is_unique))
+    }
+
+    /// The doc comments of a generated type: `summary`, followed by the doc
+    /// comments of the declared type.
+    pub(crate) fn docs_with_summary(&self, summary: &str) -> TokenStream {
+        let docs = &self.docs;
+        if docs.is_empty() {
+            quote!(#[doc = #summary])
+        } else {
+            quote! {
+                #[doc = #summary]
+                #[doc = ""]
+                #(#docs)*
+            }
+        }
+    }
+}
+
+/// Only `Vec<u8>` is stored in a column. A nested Vec would otherwise fail
+/// with errors about missing traits deep inside the generated code, so it is
+/// rejected on the field instead.
+fn check_nested_vecs(fields: &[(AttributeFieldData, syn::Field)]) -> Result<(), Error> {
+    for (attributes, field) in fields {
+        if attributes.is_skip || attributes.is_json {
+            continue;
+        }
+        if vec_element(&field.ty).and_then(vec_element).is_some() {
+            return Err(Error::new(
+                field.ty.span(),
+                crate::error::ErrorKind::NestedVec,
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// New rows leave a `skip_insert` column to its default, so without one it
+/// would be NULL. Non-optional columns are NOT NULL, which would make every
+/// insert fail, so such fields need a default or an `Option` type. A primary
+/// key is assigned by SQLite instead.
+fn check_skip_inserts(fields: &[(AttributeFieldData, syn::Field)]) -> Result<(), Error> {
+    for (attributes, field) in fields {
+        if !attributes.is_skip_insert || attributes.is_primary || attributes.default.is_some() {
+            continue;
+        }
+        if type_argument(&field.ty, "Option").is_none() {
+            return Err(Error::new(
+                field.ty.span(),
+                crate::error::ErrorKind::SkipInsertWithoutDefault,
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// The element type of `Vec<T>`, or None if `type_` is no Vec.
+fn vec_element(type_: &Type) -> Option<&Type> {
+    type_argument(type_, "Vec")
+}
+
+/// The `T` of `wrapper<T>`, or None if `type_` is no `wrapper`. Only the
+/// last path segment is compared, so aliases are not recognized.
+fn type_argument<'a>(type_: &'a Type, wrapper: &str) -> Option<&'a Type> {
+    let Type::Path(path) = type_ else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if path.qself.is_some() || segment.ident != wrapper {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+    match arguments.args.first()? {
+        syn::GenericArgument::Type(element) => Some(element),
+        _ => None,
+    }
 }
 
 impl ToTokens for StructData {
@@ -372,9 +669,11 @@ impl ToTokens for StructData {
         );
         let visibility = &self.visibility;
         let name = &self.name;
+        let generics = &self.generics;
+        let where_clause = &self.generics.where_clause;
         let members = &self.fields();
         let iter = quote! {
-            #visibility struct #name {
+            #visibility struct #name #generics #where_clause {
                 #(#members,)*
             }
         };
@@ -17,6 +17,15 @@ pub enum ErrorKind {
     MultipleConflictAttributes,
     InvalidAttribute(String),
     NoColumns,
+    TransparentNeedsOneField,
+    EnumNeedsUnitVariants,
+    UnknownField(String),
+    GenericTable,
+    GenericEnum,
+    NestedVec,
+    SoftDeleteColumnTaken,
+    SkipInsertWithoutDefault,
+    ReplaceNeedsUniqueFields,
 }
 
 impl Display for ErrorKind {
@@ -36,6 +45,54 @@ impl Display for ErrorKind {
             ErrorKind::NoColumns => {
                 write!(f, "No columns on this struct, nothing to put into a table.")
             }
+            ErrorKind::TransparentNeedsOneField => {
+                write!(
+                    f,
+                    "#[silo(transparent)] needs a struct with exactly one field."
+                )
+            }
+            ErrorKind::EnumNeedsUnitVariants => {
+                write!(
+                    f,
+                    "Only enums, whose variants have no fields, can be stored in a column."
+                )
+            }
+            ErrorKind::UnknownField(field) => {
+                write!(f, "No field named {field} in this struct.")
+            }
+            ErrorKind::GenericTable => {
+                write!(
+                    f,
+                    "A table has a single name, so it cannot be generic. Derive ToColumns for the generic part instead."
+                )
+            }
+            ErrorKind::GenericEnum => {
+                write!(f, "Only structs deriving ToColumns can be generic.")
+            }
+            ErrorKind::NestedVec => {
+                write!(
+                    f,
+                    "Nested collections cannot be stored in columns. Use #[silo(json)] to store the value as JSON."
+                )
+            }
+            ErrorKind::SoftDeleteColumnTaken => {
+                write!(
+                    f,
+                    "#[silo(soft_delete)] adds the column deleted_at, so no field can be named like it."
+                )
+            }
+            ErrorKind::SkipInsertWithoutDefault => {
+                write!(
+                    f,
+                    "New rows would leave this #[silo(skip_insert)] column NULL. Add #[silo(default = ..)] or make it an Option."
+                )
+            }
+            ErrorKind::ReplaceNeedsUniqueFields => {
+                write!(
+                    f,
+                    "#[silo(replace(..))] needs a single primary or unique field, or exactly the fields of a #[silo(unique(..))]."
+                )
+            }
         }
     }
 }
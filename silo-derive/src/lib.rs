@@ -1,5 +1,6 @@
 use proc_macro::TokenStream;
 use quote::ToTokens;
+use syn::spanned::Spanned;
 
 mod to_table;
 use to_table::ToTableStruct;
@@ -35,16 +36,147 @@ mod error;
 /// struct EmptyColumns {}
 /// ```
 ///
+/// Nested collections have no columns, store them with `#[silo(json)]`
+/// instead.
+///
+/// ```compile_fail
+/// # use silo_derive::ToTable;
+/// #[derive(Debug, Clone, ToTable)]
+/// struct Matrix {
+///     rows: Vec<Vec<u32>>,
+/// }
+/// ```
+///
+/// A table has a single name, so it cannot be generic.
+///
+/// ```compile_fail
+/// # use silo_derive::ToTable;
+/// #[derive(Debug, Clone, ToTable)]
+/// struct GenericTable<T> {
+///     value: T,
+/// }
+/// ```
+///
 /// # Attributes
 ///
 /// ## Struct Attributes
 ///
+/// **#[[silo(strict)]]**
+///
+/// Creates the table as a [STRICT](https://sqlite.org/stricttables.html)
+/// table, so sqlite rejects values, which do not match the type of their
+/// column, instead of storing them anyway. This only affects new tables.
+///
+/// ```ignore
+/// #[derive(ToTable)]
+/// #[silo(strict)]
+/// struct Measurement {
+///     #[silo(primary)]
+///     id: u32,
+///     value: f64,
+/// }
+/// ```
+///
+/// **#[[silo(unique(field, ..))]]**
+///
+/// The listed fields are unique together, so inserting a second row with the
+/// same combination of values violates a constraint and is handled like any
+/// other conflict. Unlike `#[silo(unique)]` on a single field, every field may
+/// repeat on its own. The fields have to be stored in a single column. This
+/// only affects new tables.
+///
+/// ```ignore
+/// #[derive(ToTable)]
+/// #[silo(unique(movie_id, genre_id))]
+/// struct MovieWithGenre {
+///     movie_id: u32,
+///     genre_id: u32,
+/// }
+/// ```
+///
+/// **#[[silo(replace(field, ..))]]**
+///
+/// Like `#[silo(replace)]`, inserting a row, which conflicts with an existing
+/// one, replaces it. But only conflicts on the listed fields are replaced, by
+/// updating all other columns of the existing row, like `upsert` does.
+/// Conflicts on other unique columns still fail the insert. The fields have to
+/// be a single primary key or `#[silo(unique)]` field, or exactly the fields of
+/// a `#[silo(unique(..))]`, each stored in a single column. A
+/// `#[silo(unique_index)]` does not cover NULL, so it cannot be replaced on.
+///
+/// ```ignore
+/// #[derive(ToTable)]
+/// #[silo(replace(email))]
+/// struct User {
+///     #[silo(primary)]
+///     id: u32,
+///     #[silo(unique)]
+///     email: String,
+///     #[silo(unique)]
+///     name: String,
+/// }
+/// ```
+///
+/// **#[[silo(generate_insert_struct)]]**
+///
+/// Generates a struct `New{Name}` without the primary key and the fields,
+/// which are never inserted, together with `{Name}Table::insert_new`. So the
+/// database assigns these columns and they cannot be given by accident. Like
+/// the partial struct, it uses the storage type of `#[silo(json)]` fields.
+///
+/// ```ignore
+/// #[derive(ToTable)]
+/// #[silo(generate_insert_struct)]
+/// struct Genre {
+///     #[silo(primary)]
+///     id: i64,
+///     name: String,
+/// }
+///
+/// genres.insert_new(NewGenre { name: "Drama".into() })?;
+/// ```
+///
+/// **#[[silo(soft_delete)]]**
+///
+/// Adds the column `deleted_at` to the table, which is not part of the struct.
+/// Deleting rows only sets it to the current time, and the filter of the table
+/// skips deleted rows, so they are neither loaded, updated nor deleted again.
+/// Call `include_deleted` on the filter to match them as well. Deleted rows
+/// still hold their primary key and unique values.
+///
+/// ```ignore
+/// #[derive(ToTable)]
+/// #[silo(soft_delete)]
+/// struct Note {
+///     #[silo(primary)]
+///     id: i64,
+///     text: String,
+/// }
+///
+/// notes.delete(1)?;
+/// let all_notes = notes.load_where(NoteFilter::default().include_deleted())?;
+/// ```
+///
+/// Since the table already has a column `deleted_at`, no field can be named
+/// like it.
+///
+/// ```compile_fail
+/// # use silo_derive::ToTable;
+/// #[derive(ToTable)]
+/// #[silo(soft_delete)]
+/// struct Note {
+///     text: String,
+///     deleted_at: Option<String>,
+/// }
+/// ```
+///
 /// ## Field Attributes
 ///
 /// **#[[silo(primary)]]**
 ///
 /// You can designate one field as primary field. If you have multiple fields
-/// marked as primary, compilation will fail.
+/// marked as primary, compilation will fail. The struct then implements
+/// `silo::HasPrimaryKey`, which returns a reference to this field.
 ///
 /// ```compile_fail
 /// # use silo_derive::ToTable;
@@ -57,6 +189,23 @@ mod error;
 /// }
 /// ```
 ///
+/// **#[[silo(unique)]]**
+///
+/// Adds a `UNIQUE` constraint to every column of this field. Inserting a row,
+/// which would violate it, does not insert anything. On an `Option<T>` field
+/// sqlite treats NULLs as distinct, so any number of rows may have `None`,
+/// while `Some` values have to be unique.
+///
+/// ```ignore
+/// #[derive(ToTable)]
+/// struct Movie {
+///     #[silo(primary)]
+///     id: u32,
+///     #[silo(unique)]
+///     imdb_id: Option<String>,
+/// }
+/// ```
+///
 /// **#[[silo(skip)]]**
 ///
 /// Any field, which can not be represented in a database, or which you do not
@@ -75,11 +224,162 @@ mod error;
 ///     marker: PhantomMarker<T>,
 /// }
 /// ```
-
+///
+/// **#[[silo(skip_insert)]]** and **#[[silo(skip_select)]]**
+///
+/// The column of a `skip_insert` field is created, but left out of inserts,
+/// so new rows get its default. So it needs a default, unless it is an
+/// `Option`. A `skip_select` field is written, but never
+/// read back and filled with `Default` instead.
+///
+/// ```ignore
+/// #[derive(ToTable)]
+/// struct Post {
+///     #[silo(primary)]
+///     id: u32,
+///     #[silo(skip_insert)]
+///     #[silo(default = 0)]
+///     views: u32,
+///     #[silo(skip_select)]
+///     draft: String,
+/// }
+/// ```
+///
+/// **#[[silo(autoincrement)]]**
+///
+/// Lets SQLite assign the value of an integer primary key. The field is left
+/// out of inserts, so any placeholder value can be inserted. Use
+/// `insert_returning_id` to get the assigned value.
+///
+/// ```ignore
+/// #[derive(ToTable)]
+/// struct Comment {
+///     #[silo(primary)]
+///     #[silo(autoincrement)]
+///     id: i64,
+///     text: String,
+/// }
+/// ```
+///
+/// **#[[silo(index)]]**
+///
+/// Creates an index named `idx_{table}_{column}` for every column of this
+/// field, when the table is loaded.
+///
+/// ```ignore
+/// #[derive(ToTable)]
+/// struct Track {
+///     #[silo(primary)]
+///     id: u32,
+///     #[silo(index)]
+///     artist: String,
+/// }
+/// ```
+///
+/// **#[[silo(nocase)]]**
+///
+/// Creates the columns of this field with `COLLATE NOCASE`, so comparisons,
+/// filters and a `unique` constraint ignore the case of ASCII letters.
+///
+/// ```ignore
+/// #[derive(ToTable)]
+/// struct User {
+///     #[silo(primary)]
+///     id: u32,
+///     #[silo(unique)]
+///     #[silo(nocase)]
+///     name: String,
+/// }
+/// ```
+///
+/// **#[[silo(unique_index)]]**
+///
+/// Creates a partial unique index named `uidx_{table}_{column}` for every
+/// column of this field, which only covers rows where the column is not NULL.
+/// Unlike `unique` it is created when the table is loaded, so it can be added
+/// to existing tables.
+///
+/// ```ignore
+/// #[derive(ToTable)]
+/// struct Movie {
+///     #[silo(primary)]
+///     id: u32,
+///     #[silo(unique_index)]
+///     imdb_id: Option<String>,
+/// }
+/// ```
+///
+/// **#[[silo(json)]]**
+///
+/// Stores any type implementing `serde::Serialize` and
+/// `serde::de::DeserializeOwned` as JSON text in a single column. Requires the
+/// `serde` feature of silo. Filters on this field compare the JSON text.
+///
+/// ```ignore
+/// #[derive(ToTable)]
+/// struct Track {
+///     #[silo(primary)]
+///     id: u32,
+///     #[silo(json)]
+///     tags: Vec<String>,
+/// }
+/// ```
+///
+/// **#[[silo(default = <lit>)]]**
+///
+/// Gives the column a literal default. When an existing table misses the
+/// column, it is added on load, and rows where it is NULL read the default.
+///
+/// ```ignore
+/// #[derive(ToTable)]
+/// struct Measurement {
+///     value: f64,
+///     #[silo(default = 55)]
+///     offset: i32,
+/// }
+/// ```
+///
+/// **#[[silo(references = <table>)]]**
+///
+/// Adds a `REFERENCES` constraint to the primary key of another table. SQLite
+/// only enforces it with `foreign_keys` turned on, see `silo::Pragmas`. Use it
+/// on fields with a single column.
+///
+/// ```ignore
+/// #[derive(ToTable)]
+/// struct Book {
+///     #[silo(primary)]
+///     id: u32,
+///     #[silo(references = Author)]
+///     author: u32,
+/// }
+/// ```
+///
+/// **#[[silo(check = "<sql>")]]**
+///
+/// Adds a `CHECK` constraint with the given condition to every column of this
+/// field, so sqlite rejects rows, which do not satisfy it. The condition is
+/// inserted as is and refers to columns by their name. This only affects new
+/// tables and columns added on load.
+///
+/// ```ignore
+/// #[derive(ToTable)]
+/// struct Person {
+///     #[silo(primary)]
+///     id: u32,
+///     #[silo(check = "age >= 0")]
+///     age: i32,
+/// }
+/// ```
 pub fn derive_to_table(input: TokenStream) -> TokenStream {
     // syn::Data
     let input: syn::DeriveInput = syn::parse(input)
         .expect("This is a derive macro and should be used with structs or enums.");
+    if !input.generics.params.is_empty() {
+        return error::Error::new(input.generics.span(), error::ErrorKind::GenericTable)
+            .into_token_stream()
+            .into();
+    }
 
     let base = match input.data {
         syn::Data::Struct(data_struct) => {
@@ -99,18 +399,97 @@ pub fn derive_to_table(input: TokenStream) -> TokenStream {
 }
 
 #[proc_macro_derive(ToColumns, attributes(silo))]
+/// This allows you to use your struct as the type of a field in a table. Its
+/// fields are stored in columns prefixed with the name of that field.
+///
+/// # Attributes
+///
+/// ## Struct Attributes
+///
+/// **#[[silo(transparent)]]**
+///
+/// A struct with exactly one field can be stored, filtered and read exactly
+/// like that field, without any prefix.
+///
+/// ```ignore
+/// #[derive(ToColumns)]
+/// #[silo(transparent)]
+/// struct Email(String);
+///
+/// #[derive(ToTable)]
+/// struct User {
+///     #[silo(primary)]
+///     email: Email,
+/// }
+/// ```
+///
+/// ## Generics
+///
+/// A struct may have generic parameters, every field, whose type uses one of
+/// them, has to be stored in columns itself. Tables and enums cannot be
+/// generic.
+///
+/// ```ignore
+/// #[derive(ToColumns)]
+/// struct Range<T> {
+///     start: T,
+///     end: T,
+/// }
+///
+/// #[derive(ToTable)]
+/// struct Booking {
+///     #[silo(primary)]
+///     id: u32,
+///     days: Range<u32>,
+/// }
+/// ```
+///
+/// ## Enums
+///
+/// An enum, whose variants have no fields, is stored in a single column,
+/// which holds the name of the variant.
+///
+/// **#[[silo(repr_int)]]**
+///
+/// Stores the index of the variant in declaration order as an `INTEGER`
+/// instead of its name. Explicit discriminants are ignored. Switching between
+/// names and indices, or reordering the variants, changes how existing rows
+/// are read, so it needs a migration of the stored data.
+///
+/// ```ignore
+/// #[derive(ToColumns)]
+/// #[silo(repr_int)]
+/// enum Fruit {
+///     Apple,
+///     Banana,
+/// }
+///
+/// #[derive(ToTable)]
+/// struct Basket {
+///     #[silo(primary)]
+///     id: u32,
+///     fruit: Fruit,
+/// }
+/// ```
 pub fn derive_to_columns(input: TokenStream) -> TokenStream {
     // syn::Data
     let input: syn::DeriveInput = syn::parse(input)
         .expect("This is a derive macro and should be used with structs or enums.");
 
     let base = match input.data {
-        syn::Data::Struct(data_struct) => {
-            ToColumnsStruct::from_struct(input.attrs, input.ident, input.vis, data_struct)
-        }
-        syn::Data::Enum(_data_enum) => {
-            panic!("Enums are currently not supported.")
-        }
+        syn::Data::Struct(data_struct) => ToColumnsStruct::from_struct(
+            input.attrs,
+            input.ident,
+            input.vis,
+            input.generics,
+            data_struct,
+        )
+        .map(|it| it.into_token_stream()),
+        syn::Data::Enum(_) if !input.generics.params.is_empty() => Err(error::Error::new(
+            input.generics.span(),
+            error::ErrorKind::GenericEnum,
+        )),
+        syn::Data::Enum(data_enum) => to_columns::from_enum(input.attrs, input.ident, data_enum),
         syn::Data::Union(_) => {
             panic!("Unions need a clear representation, either use a struct or an enum.")
         }
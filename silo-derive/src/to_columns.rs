@@ -1,41 +1,87 @@
-use crate::base_struct;
+use crate::{attributes, base_struct};
 use quote::ToTokens;
-use syn::{Ident, Visibility};
+use syn::{Ident, Visibility, spanned::Spanned};
 
 mod as_params;
 mod extract_from_row;
 mod filterable;
 mod partial;
+mod transparent;
+mod unit_enum;
 
 pub struct ToColumnsStruct {
-    visibility: Visibility,
     base_struct: base_struct::StructData,
+    transparent: Option<transparent::TransparentStruct>,
 }
 
 impl ToColumnsStruct {
     pub fn from_struct(
-        _attrs: Vec<syn::Attribute>,
+        attrs: Vec<syn::Attribute>,
         name: Ident,
         visibility: Visibility,
+        generics: syn::Generics,
         data_struct: syn::DataStruct,
     ) -> Result<Self, crate::error::Error> {
-        // let attribute_struct_data = attributes::ToTableAttributesStruct::parse(&attrs);
-        // let on_conflict = attribute_struct_data.on_conflict();
+        let attribute_struct_data = attributes::ToColumnsAttributesStruct::parse(&attrs)?;
+        if attribute_struct_data.is_repr_int {
+            return Err(crate::error::Error::new(
+                name.span(),
+                crate::error::ErrorKind::InvalidAttribute("repr_int".into()),
+            ));
+        }
+        let transparent = if attribute_struct_data.is_transparent {
+            if data_struct.fields.len() != 1 {
+                return Err(crate::error::Error::new(
+                    data_struct.fields.span(),
+                    crate::error::ErrorKind::TransparentNeedsOneField,
+                ));
+            }
+            Some(transparent::TransparentStruct::new(
+                name.clone(),
+                generics.clone(),
+                &data_struct.fields,
+            ))
+        } else {
+            None
+        };
 
         let base_struct: base_struct::StructData = base_struct::StructData::from_struct_data(
-            visibility.clone(),
+            &attrs,
+            visibility,
             name.clone(),
             data_struct.fields,
-        )?;
+        )?
+        .with_generics(generics);
         Ok(Self {
-            visibility,
             base_struct,
+            transparent,
         })
     }
 }
 
+/// Creates the column of an enum without data, see [`unit_enum::UnitEnum`].
+pub fn from_enum(
+    attrs: Vec<syn::Attribute>,
+    name: Ident,
+    data_enum: syn::DataEnum,
+) -> Result<proc_macro2::TokenStream, crate::error::Error> {
+    let attribute_struct_data = attributes::ToColumnsAttributesStruct::parse(&attrs)?;
+    if attribute_struct_data.is_transparent {
+        return Err(crate::error::Error::new(
+            name.span(),
+            crate::error::ErrorKind::InvalidAttribute("transparent".into()),
+        ));
+    }
+    let unit_enum = unit_enum::UnitEnum::new(name, data_enum, attribute_struct_data.is_repr_int)?;
+    Ok(unit_enum.into_token_stream())
+}
+
 impl ToTokens for ToColumnsStruct {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        if let Some(transparent) = &self.transparent {
+            transparent.to_tokens(tokens);
+            return;
+        }
         partial::impl_to_partial(tokens, &self.base_struct);
         filterable::impl_filterable(tokens, &self.base_struct);
         extract_from_row::impl_extract_from_row(tokens, &self.base_struct);
@@ -7,21 +7,26 @@ pub(crate) fn impl_extract_from_row(
     base_struct: &crate::base_struct::StructData,
 ) {
     let name = &base_struct.name;
+    let (impl_generics, type_generics, where_clause) = base_struct.generics.split_for_impl();
     let fields = base_struct.fields();
     let field_names = fields.iter().map(|f| f.name).collect_vec();
-    let field_names_literals = fields.iter().map(|f| {
+    let field_values = fields.iter().map(|f| {
         let n = f.name.unraw();
-        LitStr::new(&n.to_string(), n.span())
+        let name = LitStr::new(&n.to_string(), n.span());
+        let type_ = f.type_;
+        if f.is_skip_select {
+            return quote!(Default::default());
+        }
+        f.unwrap_storage(quote!(<#type_>::try_from_row_simple(&[column_name, concat!("_", #name)].concat(), row)?))
     });
-    let field_types = fields.iter().map(|f| f.type_).collect_vec();
     tokens.extend(quote! {
-        impl silo::ExtractFromRow for #name {
+        impl #impl_generics silo::ExtractFromRow for #name #type_generics #where_clause {
             fn try_from_row_simple(column_name: &str, row: &silo::rusqlite::Row) -> std::result::Result<Self, silo::Error> {
                 let mut result = std::mem::MaybeUninit::uninit();
-                let ptr: *mut #name = result.as_mut_ptr();
+                let ptr: *mut Self = result.as_mut_ptr();
                 #(
                     unsafe {
-                        (&raw mut (*ptr).#field_names).write(<#field_types>::try_from_row_simple(&[column_name, concat!("_", #field_names_literals)].concat(), row)?);
+                        (&raw mut (*ptr).#field_names).write(#field_values);
                     }
                 )*
                 Ok(unsafe {
@@ -0,0 +1,104 @@
+use proc_macro2::TokenStream;
+use quote::{ToTokens, quote};
+use syn::{Ident, Type};
+
+/// A struct with a single field, which is stored exactly like that field.
+pub(crate) struct TransparentStruct {
+    name: Ident,
+    generics: syn::Generics,
+    member: syn::Member,
+    type_: Type,
+}
+
+impl TransparentStruct {
+    /// If the struct has type parameters, the impls require the type of the
+    /// field to be stored in columns.
+    pub(crate) fn new(name: Ident, mut generics: syn::Generics, fields: &syn::Fields) -> Self {
+        let field = fields
+            .iter()
+            .next()
+            .expect("Transparent structs have exactly one field.");
+        let member = match &field.ident {
+            Some(ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(0.into()),
+        };
+        let type_ = field.ty.clone();
+        if generics.type_params().next().is_some() {
+            generics
+                .make_where_clause()
+                .predicates
+                .push(syn::parse_quote! {
+                    #type_: silo::AsColumns
+                        + silo::AsParams
+                        + silo::ExtractFromRow
+                        + silo::filter::Filterable
+                });
+        }
+        Self {
+            name,
+            generics,
+            member,
+            type_,
+        }
+    }
+}
+
+impl ToTokens for TransparentStruct {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let Self {
+            name,
+            generics,
+            member,
+            type_,
+        } = self;
+        let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
+        let name = quote!(#name #type_generics);
+        tokens.extend(quote! {
+            impl #impl_generics silo::AsColumns for #name #where_clause {
+                const COLUMN_COUNT: usize = <#type_ as silo::AsColumns>::COLUMN_COUNT;
+            }
+
+            impl #impl_generics silo::AsColumnsDynamicallySized for #name #where_clause {
+                fn columns(parent: Option<&str>, is_unique: bool, is_primary: bool) -> Vec<silo::SqlColumn> {
+                    <#type_ as silo::AsColumnsDynamicallySized>::columns(parent, is_unique, is_primary)
+                }
+            }
+
+            impl #impl_generics silo::AsParams for #name #where_clause {
+                fn as_params<'a>(&'a self) -> Vec<silo::ToSqlDyn<'a>> {
+                    silo::AsParams::as_params(&self.#member)
+                }
+            }
+
+            impl #impl_generics silo::ExtractFromRow for #name #where_clause {
+                fn try_from_row_simple(column_name: &str, row: &silo::rusqlite::Row) -> std::result::Result<Self, silo::Error> {
+                    Ok(Self {
+                        #member: <#type_ as silo::ExtractFromRow>::try_from_row_simple(column_name, row)?,
+                    })
+                }
+
+                fn try_from_row(
+                    column_name: &str,
+                    row: &silo::rusqlite::Row,
+                    connection: &silo::rusqlite::Connection,
+                ) -> std::result::Result<Self, silo::Error> {
+                    Ok(Self {
+                        #member: <#type_ as silo::ExtractFromRow>::try_from_row(column_name, row, connection)?,
+                    })
+                }
+            }
+
+            impl #impl_generics silo::partial::HasPartial for #name #where_clause {
+                type Partial = Option<#name>;
+            }
+
+            impl #impl_generics silo::filter::Filterable for #name #where_clause {
+                type Filter = <#type_ as silo::filter::Filterable>::Filter;
+
+                fn convert_to_equals_filter(self) -> Self::Filter {
+                    silo::filter::Filterable::convert_to_equals_filter(self.#member)
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,116 @@
+use proc_macro2::TokenStream;
+use quote::{ToTokens, quote};
+use syn::{Ident, spanned::Spanned};
+
+/// An enum without any data in its variants, which is stored in a single
+/// column. By default the column holds the name of the variant, with
+/// `#[silo(repr_int)]` it holds the index of the variant instead.
+pub(crate) struct UnitEnum {
+    name: Ident,
+    variants: Vec<Ident>,
+    is_repr_int: bool,
+}
+
+impl UnitEnum {
+    pub(crate) fn new(
+        name: Ident,
+        data_enum: syn::DataEnum,
+        is_repr_int: bool,
+    ) -> Result<Self, crate::error::Error> {
+        let variants = data_enum
+            .variants
+            .into_iter()
+            .map(|v| match v.fields {
+                syn::Fields::Unit => Ok(v.ident),
+                fields => Err(crate::error::Error::new(
+                    fields.span(),
+                    crate::error::ErrorKind::EnumNeedsUnitVariants,
+                )),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            name,
+            variants,
+            is_repr_int,
+        })
+    }
+}
+
+impl ToTokens for UnitEnum {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let Self {
+            name,
+            variants,
+            is_repr_int,
+        } = self;
+        let (storage, stored, scrutinee) = if *is_repr_int {
+            let stored = (0..variants.len())
+                .map(|i| syn::LitInt::new(&format!("{i}i64"), name.span()).into_token_stream())
+                .collect::<Vec<_>>();
+            (quote!(i64), stored, quote!(value))
+        } else {
+            let stored = variants
+                .iter()
+                .map(|v| syn::LitStr::new(&v.to_string(), v.span()).into_token_stream())
+                .collect::<Vec<_>>();
+            (quote!(String), stored, quote!(value.as_str()))
+        };
+        let name_str_lit = syn::LitStr::new(&name.to_string(), name.span());
+        tokens.extend(quote! {
+            impl silo::AsColumns for #name {
+                const COLUMN_COUNT: usize = <#storage as silo::AsColumns>::COLUMN_COUNT;
+            }
+
+            impl silo::AsColumnsDynamicallySized for #name {
+                fn columns(parent: Option<&str>, is_unique: bool, is_primary: bool) -> Vec<silo::SqlColumn> {
+                    <#storage as silo::AsColumnsDynamicallySized>::columns(parent, is_unique, is_primary)
+                }
+            }
+
+            impl silo::AsParams for #name {
+                fn as_params<'a>(&'a self) -> Vec<silo::ToSqlDyn<'a>> {
+                    vec![silo::ToSqlDyn::Borrowed(match self {
+                        #(Self::#variants => &#stored,)*
+                    })]
+                }
+            }
+
+            impl silo::ExtractFromRow for #name {
+                fn try_from_row_simple(column_name: &str, row: &silo::rusqlite::Row) -> std::result::Result<Self, silo::Error> {
+                    let value = <#storage as silo::ExtractFromRow>::try_from_row_simple(column_name, row)?;
+                    match #scrutinee {
+                        #(#stored => Ok(Self::#variants),)*
+                        _ => Err(silo::Error::IllFormattedColumn(#name_str_lit.into(), value.to_string(), None)),
+                    }
+                }
+            }
+
+            impl silo::partial::HasPartial for #name {
+                type Partial = Option<#name>;
+            }
+
+            impl silo::filter::Filterable for #name {
+                type Filter = <#storage as silo::filter::Filterable>::Filter;
+
+                fn convert_to_equals_filter(self) -> Self::Filter {
+                    silo::filter::Filterable::convert_to_equals_filter(<#storage>::from(match self {
+                        #(Self::#variants => #stored,)*
+                    }))
+                }
+            }
+
+            impl silo::filter::InFilterable for #name {
+                fn convert_to_in_filter(values: Vec<Self>) -> Self::Filter {
+                    <#storage as silo::filter::InFilterable>::convert_to_in_filter(
+                        values
+                            .into_iter()
+                            .map(|v| <#storage>::from(match v {
+                                #(Self::#variants => #stored,)*
+                            }))
+                            .collect(),
+                    )
+                }
+            }
+        });
+    }
+}
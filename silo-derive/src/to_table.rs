@@ -1,5 +1,5 @@
 use quote::{ToTokens, quote};
-use syn::{Ident, Visibility};
+use syn::{Ident, Visibility, ext::IdentExt};
 
 use crate::{attributes, base_struct};
 
@@ -9,13 +9,17 @@ pub mod from_row;
 mod from_row_type;
 mod into_sql_table;
 pub mod partial;
-mod row_type;
 
 pub struct ToTableStruct {
     visibility: Visibility,
     variants: Option<Vec<Ident>>,
     base_struct: base_struct::StructData,
     on_conflict: proc_macro2::TokenStream,
+    is_strict: bool,
+    unique_constraints: Vec<Vec<Ident>>,
+    replace_conflict_fields: Vec<Ident>,
+    generate_insert_struct: bool,
+    is_soft_delete: bool,
 }
 
 impl std::fmt::Debug for ToTableStruct {
@@ -37,15 +41,22 @@ impl ToTableStruct {
         let on_conflict = attribute_struct_data.on_conflict();
 
         let base_struct: base_struct::StructData = base_struct::StructData::from_struct_data(
+            &attrs,
             visibility.clone(),
             name.clone(),
             data_struct.fields,
         )?;
+        check_table_attributes(&attribute_struct_data, &base_struct)?;
         Ok(Self {
             visibility,
             variants: None,
             base_struct,
             on_conflict,
+            is_strict: attribute_struct_data.is_strict,
+            unique_constraints: attribute_struct_data.unique_constraints,
+            replace_conflict_fields: attribute_struct_data.replace_conflict_fields,
+            generate_insert_struct: attribute_struct_data.generate_insert_struct,
+            is_soft_delete: attribute_struct_data.is_soft_delete,
         })
     }
 
@@ -59,16 +70,23 @@ impl ToTableStruct {
         let on_conflict = attribute_struct_data.on_conflict();
         let variants = data_enum.variants.iter().map(|v| v.ident.clone()).collect();
         let base_struct: base_struct::StructData = base_struct::StructData::from_enum_data(
+            &attrs,
             visibility.clone(),
             name.clone(),
             data_enum.variants,
         )?;
+        check_table_attributes(&attribute_struct_data, &base_struct)?;
 
         Ok(Self {
             visibility,
             variants: Some(variants),
             on_conflict,
             base_struct,
+            is_strict: attribute_struct_data.is_strict,
+            unique_constraints: attribute_struct_data.unique_constraints,
+            replace_conflict_fields: attribute_struct_data.replace_conflict_fields,
+            generate_insert_struct: false,
+            is_soft_delete: attribute_struct_data.is_soft_delete,
         })
     }
 
@@ -76,42 +94,107 @@ impl ToTableStruct {
         let ToTableStruct {
             visibility,
             base_struct,
+            on_conflict,
             ..
         } = self;
         let table_name = base_struct.table_name();
         let value_type_name = &base_struct.name;
         let filter_name = base_struct.filter_name();
         let partial_name = base_struct.partial_name();
+        let get_many = base_struct.primary_key_field().map(|pk| {
+            let pk_name = pk.name;
+            let pk_type = pk.type_;
+            let key = pk.wrap_storage(quote!(row.#pk_name.clone()));
+            quote! {
+                impl<'a> #table_name<'a> {
+                    /// Loads all rows with one of the given primary keys in a
+                    /// single query. Keys without a row are missing from the
+                    /// result.
+                    pub fn get_many<K>(&self, keys: Vec<K>) -> std::result::Result<std::collections::HashMap<K, #value_type_name>, silo::Error>
+                    where
+                        K: silo::filter::InFilterable<Filter = <#pk_type as silo::filter::Filterable>::Filter> + std::hash::Hash + Eq,
+                        #pk_type: Into<K>,
+                    {
+                        silo::get_many::<#value_type_name, #filter_name, K>(
+                            &self.connection,
+                            silo::SqlTable::table_name(self),
+                            keys,
+                            |#pk_name| #filter_name {
+                                #pk_name,
+                                ..Default::default()
+                            },
+                            |row| #key.into(),
+                        )
+                    }
+                }
+            }
+        });
+
+        let primary_key = base_struct
+            .primary_key_field()
+            .filter(|pk| self.variants.is_none() && !pk.is_json)
+            .map(|pk| {
+                let pk_name = pk.name;
+                let pk_type = pk.type_;
+                quote! {
+                    impl silo::HasPrimaryKey for #value_type_name {
+                        type PrimaryKey = #pk_type;
 
+                        fn primary_key(&self) -> &Self::PrimaryKey {
+                            &self.#pk_name
+                        }
+                    }
+                }
+            });
+
+        let docs = format!(
+            "The table storing the rows of `{value_type_name}`, see `silo::Database::load`."
+        );
         quote! {
+            #[doc = #docs]
             #visibility struct #table_name<'a> {
                 connection: &'a silo::rusqlite::Connection,
+                schema: Option<&'a str>,
+                name: &'a str,
             }
 
             impl<'a> silo::SqlTable<'a> for #table_name<'a> {
                 type RowType = #value_type_name;
                 type ValueType = #value_type_name;
                 type FilterType = #filter_name;
+                const INSERT_FAILURE_BEHAVIOR: silo::SqlFailureBehavior = #on_conflict;
 
                 fn connection(&self) -> &'a silo::rusqlite::Connection {
                     self.connection
                 }
 
-                fn insert(&self, row: Self::RowType) -> std::result::Result<bool, silo::rusqlite::Error> {
-                    silo::insert_into_table(&self.connection, row)
+                fn name(&self) -> &'a str {
+                    self.name
+                }
+
+                fn schema(&self) -> Option<&'a str> {
+                    self.schema
+                }
+
+                fn insert(&self, row: Self::RowType) -> std::result::Result<bool, silo::Error> {
+                    silo::insert_into_table_with(&self.connection, silo::SqlTable::table_name(self), row, Self::INSERT_FAILURE_BEHAVIOR)
                 }
 
-                fn load_where(&self, filter: impl Into<Self::FilterType>) -> std::result::Result<Vec<Self::RowType>, silo::rusqlite::Error> {
-                    silo::load_where(&self.connection, filter)
+                fn load_where(&self, filter: impl Into<Self::FilterType>) -> std::result::Result<Vec<Self::RowType>, silo::Error> {
+                    silo::load_where(&self.connection, silo::SqlTable::table_name(self), filter)
                 }
-                fn update(&self, filter: impl Into<Self::FilterType>, updated: #partial_name) -> std::result::Result<usize, silo::rusqlite::Error> {
-                    silo::update::<#value_type_name, #partial_name, Self::FilterType>(&self.connection, filter, updated)
+                fn update(&self, filter: impl Into<Self::FilterType>, updated: #partial_name) -> std::result::Result<usize, silo::Error> {
+                    silo::update::<#value_type_name, #partial_name, Self::FilterType>(&self.connection, silo::SqlTable::table_name(self), filter, updated)
                 }
 
-                fn from_connection(connection: &'a silo::rusqlite::Connection) -> Self {
-                    Self { connection }
+                fn from_connection_with_schema(connection: &'a silo::rusqlite::Connection, schema: Option<&'a str>, name: &'a str) -> Self {
+                    Self { connection, schema, name }
                 }
             }
+
+            #get_many
+
+            #primary_key
         }
     }
 
@@ -119,15 +202,120 @@ impl ToTableStruct {
         from_row::create_from_row_for(&self.base_struct, tokens);
         partial::create_partial_for(&self.base_struct, tokens);
         as_params::create_as_params(&self.base_struct, tokens, true);
+        if self.generate_insert_struct {
+            self.create_insertable(tokens);
+        }
+    }
+
+    fn create_insertable(&self, tokens: &mut proc_macro2::TokenStream) {
+        let insertable = self.base_struct.to_insertable();
+        let visibility = &insertable.visibility;
+        let name = &insertable.name;
+        let fields = insertable.fields();
+        let field_docs = fields
+            .iter()
+            .map(|f| f.docs_or(&format!("The value of `{}`.", f.name.unraw())));
+        let table_name = self.base_struct.table_name();
+        let value_type_name = &self.base_struct.name;
+        let docs = insertable.docs_with_summary(&format!(
+            "A `{}` without the primary key and the fields, which are never inserted, see `{table_name}::insert_new`.",
+            self.base_struct.name
+        ));
+        tokens.extend(quote! {
+            #docs
+            #visibility struct #name {
+                #(#field_docs #visibility #fields,)*
+            }
+
+            impl<'a> #table_name<'a> {
+                /// Inserts a row without its primary key and the fields, which
+                /// are never inserted, so the database assigns them.
+                pub fn insert_new(&self, row: #name) -> std::result::Result<bool, silo::Error> {
+                    silo::insert_new::<#value_type_name, _>(&self.connection, silo::SqlTable::table_name(self), &row, <Self as silo::SqlTable>::INSERT_FAILURE_BEHAVIOR)
+                }
+            }
+        });
+        as_params::create_as_params(&insertable, tokens, false);
     }
 
     fn create_into_sql_table(&self) -> proc_macro2::TokenStream {
-        into_sql_table::create_into_sql_table(&self.base_struct)
+        into_sql_table::create_into_sql_table(
+            &self.base_struct,
+            self.is_strict,
+            &self.unique_constraints,
+            &self.replace_conflict_fields,
+            self.is_soft_delete,
+        )
     }
 
     fn create_filter(&self, tokens: &mut proc_macro2::TokenStream) {
-        tokens.extend(filter::create_filter_for(&self.base_struct));
+        tokens.extend(filter::create_filter_for(
+            &self.base_struct,
+            self.is_soft_delete,
+        ));
+    }
+}
+
+/// Checks the fields named by the struct attributes against the fields of the
+/// struct or enum.
+fn check_table_attributes(
+    attribute_struct_data: &attributes::ToTableAttributesStruct,
+    base_struct: &base_struct::StructData,
+) -> Result<(), crate::error::Error> {
+    if let Some(field) = attribute_struct_data
+        .unique_constraints
+        .iter()
+        .flatten()
+        .chain(&attribute_struct_data.replace_conflict_fields)
+        .find(|f| !base_struct.has_member(f))
+    {
+        return Err(crate::error::Error::new(
+            field.span(),
+            crate::error::ErrorKind::UnknownField(field.to_string()),
+        ));
+    }
+    if let Some(field) = attribute_struct_data.replace_conflict_fields.first()
+        && !is_unique_together(
+            base_struct,
+            &attribute_struct_data.unique_constraints,
+            &attribute_struct_data.replace_conflict_fields,
+        )
+    {
+        return Err(crate::error::Error::new(
+            field.span(),
+            crate::error::ErrorKind::ReplaceNeedsUniqueFields,
+        ));
+    }
+    if attribute_struct_data.is_soft_delete
+        && let Some(field) = base_struct
+            .fields()
+            .into_iter()
+            .find(|f| f.name.unraw() == "deleted_at")
+    {
+        return Err(crate::error::Error::new(
+            field.name.span(),
+            crate::error::ErrorKind::SoftDeleteColumnTaken,
+        ));
+    }
+    Ok(())
+}
+
+/// Whether `fields` are the target of a uniqueness constraint, so that ON
+/// CONFLICT can name them. Unique indices only cover values, which are not
+/// NULL, so they are no such target.
+fn is_unique_together(
+    base_struct: &base_struct::StructData,
+    unique_constraints: &[Vec<Ident>],
+    fields: &[Ident],
+) -> bool {
+    if let [field] = fields
+        && base_struct.is_unique_member(field)
+    {
+        return true;
     }
+    unique_constraints
+        .iter()
+        .any(|c| c.len() == fields.len() && c.iter().all(|f| fields.contains(f)))
 }
 
 impl ToTokens for ToTableStruct {
@@ -136,7 +324,6 @@ impl ToTokens for ToTableStruct {
         let table = self.create_table();
         tokens.extend(table);
         tokens.extend(self.create_into_sql_table());
-        // tokens.extend(self.create_row_type());
         // self.migration_handler.to_tokens(tokens);
         self.create_conversions(tokens);
         self.create_filter(tokens);
@@ -8,6 +8,8 @@ pub(crate) fn create_as_params(
     _for_table: bool,
 ) {
     let name = &base_struct.name;
+    let (impl_generics, type_generics, where_clause) = base_struct.generics.split_for_impl();
+    let name = quote!(#name #type_generics);
     let columns = base_struct.columns();
     let is_primary = columns
         .iter()
@@ -15,39 +17,96 @@ pub(crate) fn create_as_params(
     let is_unique = columns
         .iter()
         .map(|c| syn::LitBool::new(c.is_unique, c.span));
+    let is_indexed = columns
+        .iter()
+        .map(|c| syn::LitBool::new(c.is_indexed, c.span));
+    let is_unique_indexed = columns
+        .iter()
+        .map(|c| syn::LitBool::new(c.is_unique_indexed, c.span));
+    let is_nocase = columns
+        .iter()
+        .map(|c| syn::LitBool::new(c.is_nocase, c.span));
+    let default = columns.iter().map(|c| match c.default {
+        Some(d) => {
+            let sql = LitStr::new(&d.sql, c.span);
+            quote!(Some(std::borrow::Cow::Borrowed(#sql)))
+        }
+        None => quote!(None),
+    });
+    let references = columns.iter().map(|c| match c.references {
+        Some(table) => quote!(Some(std::borrow::Cow::Borrowed(
+            <#table as silo::ToTable<'static>>::NAME
+        ))),
+        None => quote!(None),
+    });
+    let check = columns.iter().map(|c| match c.check {
+        Some(check) => quote!(Some(#check)),
+        None => quote!(None),
+    });
+    let is_skip_insert = columns
+        .iter()
+        .map(|c| syn::LitBool::new(c.is_skip_insert, c.span));
+    let is_autoincrement = columns
+        .iter()
+        .map(|c| syn::LitBool::new(c.is_autoincrement, c.span));
+    let is_skip_select = columns
+        .iter()
+        .map(|c| syn::LitBool::new(c.is_skip_select, c.span));
     let column_types = columns.iter().map(|c| &c.type_).collect_vec();
     let names = columns
         .iter()
         .map(|c| format_ident!("{}", &c.name, span = c.span))
         .collect_vec();
+    let params = columns.iter().zip(&names).map(|(c, n)| {
+        if c.is_json {
+            quote!(silo::json::as_params(&self.#n))
+        } else {
+            quote!(AsParams::as_params(&self.#n))
+        }
+    });
     let names_str_lit = names.iter().map(|i| {
         let n = i.unraw();
         LitStr::new(&n.to_string(), n.span())
     });
     let as_params = quote! {
-            impl silo::AsColumns for #name {
+            impl #impl_generics silo::AsColumns for #name #where_clause {
                 const COLUMN_COUNT: usize = 0 #(+ <#column_types as silo::AsColumns>::COLUMN_COUNT)*;
             }
 
-            impl silo::AsColumnsDynamicallySized for #name {
+            impl #impl_generics silo::AsColumnsDynamicallySized for #name #where_clause {
                 fn columns(parent: Option<&str>, is_unique: bool, is_primary: bool) -> Vec<silo::SqlColumn> {
                     assert!(!is_unique);
                     assert!(!is_primary);
                     let parent = parent.map(|p| format!("{p}_")).unwrap_or_default();
                     let mut result = Vec::with_capacity(<Self as silo::AsColumns>::COLUMN_COUNT);
                     #(
-                        result.append(&mut <#column_types as silo::AsColumnsDynamicallySized>::columns(Some(&format!("{parent}{}", #names_str_lit)), #is_unique, #is_primary));
+                        result.extend(
+                            <#column_types as silo::AsColumnsDynamicallySized>::columns(Some(&format!("{parent}{}", #names_str_lit)), #is_unique, #is_primary)
+                                .into_iter()
+                                .map(|c| silo::SqlColumn {
+                                    is_indexed: c.is_indexed || #is_indexed,
+                                    is_unique_indexed: c.is_unique_indexed || #is_unique_indexed,
+                                    is_nocase: c.is_nocase || #is_nocase,
+                                    default: c.default.or(#default),
+                                    references: c.references.or(#references),
+                                    is_skip_insert: c.is_skip_insert || #is_skip_insert,
+                                    is_autoincrement: c.is_autoincrement || #is_autoincrement,
+                                    is_skip_select: c.is_skip_select || #is_skip_select,
+                                    check: silo::and_check(c.check, #check),
+                                    ..c
+                                })
+                        );
                     )*
                     result
                 }
             }
 
-            impl silo::AsParams for #name {
+            impl #impl_generics silo::AsParams for #name #where_clause {
                 fn as_params<'a>(&'a self) -> Vec<silo::ToSqlDyn<'a>> {
                     use silo::{AsParams};
                     let mut result = Vec::with_capacity(<Self as silo::AsColumns>::COLUMN_COUNT);
                     #(
-                        result.extend(AsParams::as_params(&self.#names));
+                        result.extend(#params);
                     )*
                     result
                 }
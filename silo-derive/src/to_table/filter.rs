@@ -1,13 +1,16 @@
 use itertools::Itertools;
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::{LitStr, ext::IdentExt};
 
 pub(crate) fn create_filter_for(
     base_struct: &super::base_struct::StructData,
+    is_soft_delete: bool,
 ) -> proc_macro2::TokenStream {
     let visibility = &base_struct.visibility;
     let filter_name = base_struct.filter_name();
     let name = &base_struct.name;
+    let generics = &base_struct.generics;
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
 
     let fields = base_struct
         .fields()
@@ -18,12 +21,52 @@ pub(crate) fn create_filter_for(
         let n = f.unraw();
         LitStr::new(&n.to_string(), n.span())
     });
-    let field_types = base_struct.fields().into_iter().map(|f| f.type_);
+    let field_types = base_struct
+        .fields()
+        .into_iter()
+        .map(|f| f.type_)
+        .collect_vec();
+    let is_not_methods = fields.iter().map(|f| format_ident!("{}_is_not", f.unraw()));
+    let is_one_of_methods = fields
+        .iter()
+        .map(|f| format_ident!("{}_is_one_of", f.unraw()));
+    let nan_fields = base_struct
+        .fields()
+        .into_iter()
+        .filter(|f| is_float(f.type_))
+        .collect_vec();
+    let nan_field_types = nan_fields.iter().map(|f| f.type_);
+    let is_nan_methods = nan_fields
+        .iter()
+        .map(|f| format_ident!("{}_is_nan", f.name.unraw()));
+    let nan_fields = nan_fields.iter().map(|f| f.name);
+    let text_fields = base_struct
+        .fields()
+        .into_iter()
+        .filter(|f| is_string(f.type_))
+        .map(|f| f.name)
+        .collect_vec();
+    let starts_with_methods = text_fields
+        .iter()
+        .map(|f| format_ident!("{}_starts_with", f.unraw()));
+    let ends_with_methods = text_fields
+        .iter()
+        .map(|f| format_ident!("{}_ends_with", f.unraw()));
+    let glob_methods = text_fields
+        .iter()
+        .map(|f| format_ident!("{}_glob", f.unraw()));
+    let matches_methods = text_fields
+        .iter()
+        .map(|f| format_ident!("{}_matches", f.unraw()));
+    let field_values = base_struct.fields().into_iter().map(|f| {
+        let name = f.name;
+        f.wrap_storage(quote!(self.#name))
+    });
     let from_pk = if let Some(pk) = base_struct.primary_key_field() {
         let pk_type = pk.type_;
         let pk_ident = pk.name;
         quote! {
-            impl From<#pk_type> for #filter_name {
+            impl #impl_generics From<#pk_type> for #filter_name #type_generics #where_clause {
                 fn from(#pk_ident: #pk_type) -> Self {
                     use silo::filter::Filterable;
                     Self {
@@ -36,49 +79,304 @@ pub(crate) fn create_filter_for(
     } else {
         quote! {}
     };
+    // Filters of soft delete tables skip deleted rows, unless they are
+    // included explicitly.
+    let (
+        include_deleted_field,
+        include_deleted_method,
+        include_deleted_init,
+        include_deleted_clone,
+        write_not_deleted,
+    ) = if is_soft_delete {
+        (
+            quote! {
+                /// Set by `include_deleted`, this filter also matches
+                /// deleted rows.
+                #[doc(hidden)]
+                pub __silo_include_deleted: bool,
+            },
+            quote! {
+                /// Also matches rows, which were deleted. Without it, the
+                /// rows of a `#[silo(soft_delete)]` table, which were
+                /// deleted, are never loaded, updated or deleted again. It
+                /// applies to the alternatives added with `or` as well, only
+                /// the filter they were added to decides.
+                pub fn include_deleted(mut self) -> Self {
+                    self.__silo_include_deleted = true;
+                    self
+                }
+            },
+            quote!(__silo_include_deleted: false,),
+            quote!(__silo_include_deleted: self.__silo_include_deleted,),
+            // Only the filter of the table itself decides, not the
+            // alternatives or the filter of a nested field.
+            quote! {
+                if parent.is_none() && !self.__silo_include_deleted {
+                    silo::filter::write_not_deleted(sql);
+                }
+            },
+        )
+    } else {
+        (quote!(), quote!(), quote!(), quote!(), quote!())
+    };
+    let field_docs = fields.iter().map(|f| {
+        format!(
+            "Only matches rows, where `{}` matches this filter.",
+            f.unraw()
+        )
+    });
+    let docs = base_struct.docs_with_summary(&format!(
+        "Filters rows of `{name}`. Fields, which are not set, match every row."
+    ));
     quote! {
-        #[derive(Default)]
-        #visibility struct #filter_name {
-            #(pub #fields: <#field_types as silo::filter::Filterable>::Filter,)*
+        #docs
+        #visibility struct #filter_name #generics #where_clause {
+            #(
+                #[doc = #field_docs]
+                pub #fields: <#field_types as silo::filter::Filterable>::Filter,
+            )*
+            /// Alternatives added with `or`, a row matches, if it matches this
+            /// filter or any of the alternatives.
+            #[doc(hidden)]
+            pub __silo_or: Vec<#filter_name #type_generics>,
+            /// Set by `none`, the conditions of this filter match no row.
+            #[doc(hidden)]
+            pub __silo_matches_nothing: bool,
+            #include_deleted_field
+        }
+
+        // Derived, these would require every type parameter to implement
+        // Default and Clone, instead of only the filters of the fields.
+        impl #impl_generics Default for #filter_name #type_generics #where_clause {
+            fn default() -> Self {
+                Self {
+                    #(#fields: Default::default(),)*
+                    __silo_or: Vec::new(),
+                    __silo_matches_nothing: false,
+                    #include_deleted_init
+                }
+            }
+        }
+
+        impl #impl_generics Clone for #filter_name #type_generics #where_clause {
+            fn clone(&self) -> Self {
+                Self {
+                    #(#fields: self.#fields.clone(),)*
+                    __silo_or: self.__silo_or.clone(),
+                    __silo_matches_nothing: self.__silo_matches_nothing,
+                    #include_deleted_clone
+                }
+            }
+        }
+
+        impl #impl_generics #filter_name #type_generics #where_clause {
+            /// Matches every row, same as `default`, but explicit about it.
+            pub fn all() -> Self {
+                Self::default()
+            }
+
+            /// Matches no row, e.g. to turn a conditional delete into a no-op.
+            /// Alternatives added with `or` still match their rows.
+            pub fn none() -> Self {
+                Self {
+                    __silo_matches_nothing: true,
+                    ..Default::default()
+                }
+            }
+
+            /// Matches rows, which match this filter or `other`.
+            pub fn or(mut self, other: impl Into<Self>) -> Self {
+                self.__silo_or.push(other.into());
+                self
+            }
+
+            #include_deleted_method
+
+            #(
+                /// Only matches rows, which do not match the given filter.
+                #[allow(non_snake_case)]
+                pub fn #is_not_methods<F>(mut self, filter: F) -> Self
+                where
+                    F: silo::filter::Negate + Into<<#field_types as silo::filter::Filterable>::Filter>,
+                {
+                    self.#fields = filter.negate().into();
+                    self
+                }
+
+                /// Only matches rows, where this field is one of `values`. For
+                /// fields of nested types, use this method of their filter.
+                #[allow(non_snake_case)]
+                pub fn #is_one_of_methods<V>(mut self, values: impl IntoIterator<Item = V>) -> Self
+                where
+                    V: silo::filter::InFilterable<Filter = <#field_types as silo::filter::Filterable>::Filter>,
+                {
+                    self.#fields = V::convert_to_in_filter(values.into_iter().collect());
+                    self
+                }
+            )*
+
+            #(
+                /// Only matches rows, where this field is NaN.
+                #[allow(non_snake_case)]
+                pub fn #is_nan_methods(mut self) -> Self {
+                    self.#nan_fields = silo::filter::FieldFilter::<#nan_field_types>::is_nan();
+                    self
+                }
+            )*
+
+            #(
+                /// Only matches rows, where this field starts with `prefix`.
+                #[allow(non_snake_case)]
+                pub fn #starts_with_methods(mut self, prefix: impl Into<String>) -> Self {
+                    self.#text_fields = silo::filter::FieldFilter::starts_with(prefix);
+                    self
+                }
+
+                /// Only matches rows, where this field ends with `suffix`.
+                #[allow(non_snake_case)]
+                pub fn #ends_with_methods(mut self, suffix: impl Into<String>) -> Self {
+                    self.#text_fields = silo::filter::FieldFilter::ends_with(suffix);
+                    self
+                }
+
+                /// Only matches rows, where this field matches the case-sensitive
+                /// GLOB `pattern`.
+                #[allow(non_snake_case)]
+                pub fn #glob_methods(mut self, pattern: impl Into<String>) -> Self {
+                    self.#text_fields = silo::filter::FieldFilter::glob(pattern);
+                    self
+                }
+            )*
+        }
+
+        silo::__with_regex! {
+            impl #impl_generics #filter_name #type_generics #where_clause {
+                #(
+                    /// Only matches rows, where this field matches the regular
+                    /// expression `pattern`. Only generated with the `regex`
+                    /// feature of silo.
+                    #[allow(non_snake_case)]
+                    pub fn #matches_methods(mut self, pattern: impl Into<String>) -> Self {
+                        self.#text_fields = silo::filter::FieldFilter::matches(pattern);
+                        self
+                    }
+                )*
+            }
         }
 
         #from_pk
 
-        impl From<()> for #filter_name {
+        impl #impl_generics From<()> for #filter_name #type_generics #where_clause {
             fn from((): ()) -> Self {
                 Self::default()
             }
         }
 
-        impl silo::filter::Filter for #filter_name {
+        impl #impl_generics #filter_name #type_generics #where_clause {
+            /// Writes the conditions of the fields and the alternatives,
+            /// without the ones of the table, like excluding deleted rows.
+            fn __silo_write_conditions(&self, sql: &mut String, parent: Option<&str>) {
+                use silo::filter::Filter;
+                let write_fields = |sql: &mut String| {
+                    if self.__silo_matches_nothing {
+                        silo::filter::write_matches_nothing(sql);
+                    }
+                    let parent = parent.map(|p| format!("{p}_")).unwrap_or_default();
+                    #(
+                        self.#fields.to_sql(sql, Some(&format!("{parent}{}", #fields_str_lit)));
+                    )*
+                };
+                if self.__silo_or.is_empty() {
+                    write_fields(sql);
+                } else {
+                    silo::filter::write_alternatives(
+                        sql,
+                        write_fields,
+                        self.__silo_or
+                            .iter()
+                            .map(|a| move |sql: &mut String| a.__silo_write_conditions(sql, parent)),
+                    );
+                }
+            }
+        }
+
+        impl #impl_generics silo::filter::Filter for #filter_name #type_generics #where_clause {
             fn to_sql(&self, sql: &mut String, parent: Option<&str>) {
-                let parent = parent.map(|p| format!("{p}_")).unwrap_or_default();
+                self.__silo_write_conditions(sql, parent);
+                #write_not_deleted
+            }
+
+            fn split_in_lists(self, max_params: usize) -> Vec<Self> {
+                // Splitting one alternative would load the rows matching the
+                // other alternatives once per part.
+                if !self.__silo_or.is_empty() {
+                    return vec![self];
+                }
+                let result = vec![self];
                 #(
-                    self.#fields.to_sql(sql, Some(&format!("{parent}{}", #fields_str_lit)));
+                    let result: Vec<Self> = result
+                        .into_iter()
+                        .flat_map(|filter| {
+                            filter
+                                .#fields
+                                .clone()
+                                .split_in_lists(max_params)
+                                .into_iter()
+                                .map(move |part| {
+                                    let mut filter = filter.clone();
+                                    filter.#fields = part;
+                                    filter
+                                })
+                        })
+                        .collect();
                 )*
+                result
             }
         }
 
-        impl silo::AsParams for #filter_name {
+        impl #impl_generics silo::AsParams for #filter_name #type_generics #where_clause {
             fn as_params<'a>(&'a self) -> Vec<silo::ToSqlDyn<'a>> {
                     use silo::{AsParams};
                     let mut result = Vec::new();
                     #(
                         result.extend(AsParams::as_params(&self.#fields));
                     )*
+                    for alternative in &self.__silo_or {
+                        result.extend(alternative.as_params());
+                    }
                     result
                 }
         }
 
-        impl silo::filter::Filterable for #name {
-            type Filter = #filter_name;
+        impl #impl_generics silo::filter::Filterable for #name #type_generics #where_clause {
+            type Filter = #filter_name #type_generics;
             fn convert_to_equals_filter(self) -> Self::Filter {
                 Self::Filter {
                     #(
-                        #fields: self.#fields.convert_to_equals_filter(),
+                        #fields: #field_values.convert_to_equals_filter(),
                     )*
+                    __silo_or: Vec::new(),
+                    __silo_matches_nothing: false,
+                    #include_deleted_init
                 }
             }
         }
     }
 }
+
+fn is_float(type_: &syn::Type) -> bool {
+    is_one_of(type_, &["f32", "f64"])
+}
+
+fn is_string(type_: &syn::Type) -> bool {
+    is_one_of(type_, &["String"])
+}
+
+fn is_one_of(type_: &syn::Type, names: &[&str]) -> bool {
+    match type_ {
+        syn::Type::Path(path) => {
+            path.qself.is_none() && names.iter().any(|name| path.path.is_ident(name))
+        }
+        _ => false,
+    }
+}
@@ -18,9 +18,10 @@ pub(crate) fn create_from_row_for_base_struct(
         create_try_from_row_body(base_struct)
     } else {
         let partial = base_struct.partial_name();
+        let name = LitStr::new(&name.unraw().to_string(), name.span());
         quote!(
             use silo::partial::PartialType;
-            #partial::try_from_row(row, connection)?.transpose().ok_or(silo::Error::Todo("Improve error handling here, so we know which column was missing".into()))
+            #partial::try_from_row(row, connection)?.transpose().ok_or(silo::Error::IncompleteRow(#name.into()))
         )
     };
     let from_row = quote! {
@@ -41,17 +42,21 @@ fn create_try_from_row_body(
 ) -> proc_macro2::TokenStream {
     let columns = base_struct.columns();
     let column_names: Vec<syn::Ident> = columns.iter().map(|c| c.ident()).collect();
-    let column_names_str_lit = column_names.iter().map(|c| {
-        let n = c.unraw();
-        LitStr::new(&n.to_string(), n.span())
+    let column_values = columns.iter().map(|c| {
+        let name = LitStr::new(&c.ident().unraw().to_string(), c.span);
+        let type_ = c.type_;
+        if c.is_skip_select {
+            quote!(Default::default())
+        } else {
+            quote!(<#type_ as silo::ExtractFromRow>::try_from_row(#name, row, connection)?)
+        }
     });
-    let column_types = columns.iter().map(|c| c.type_);
 
     if let Some(_variant) = base_struct.variant_field().map(|f| f.name) {
         quote! {todo!("Enums not yet supported!")}
     } else {
         quote! {#(
-            let #column_names = <#column_types as silo::ExtractFromRow>::try_from_row(#column_names_str_lit, row, connection)?;
+            let #column_names = #column_values;
         )*
         Ok(Self {
             #(#column_names,)*
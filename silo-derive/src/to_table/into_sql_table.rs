@@ -1,17 +1,71 @@
-use quote::quote;
+use quote::{quote, quote_spanned};
 use syn::{LitStr, ext::IdentExt};
 
 pub(crate) fn create_into_sql_table(
     base_struct: &super::base_struct::StructData,
+    is_strict: bool,
+    unique_constraints: &[Vec<syn::Ident>],
+    replace_conflict_fields: &[syn::Ident],
+    is_soft_delete: bool,
 ) -> proc_macro2::TokenStream {
     let name = &base_struct.name;
     let table_name = base_struct.table_name();
     let name_str_lit = LitStr::new(&name.unraw().to_string(), name.span());
+    // Fields of both are only checked once, so they fail with a single error.
+    let single_column_assertions = unique_constraints
+        .iter()
+        .flatten()
+        .chain(
+            replace_conflict_fields
+                .iter()
+                .filter(|f| !unique_constraints.iter().flatten().any(|u| u == *f)),
+        )
+        .map(|f| single_column_assertion(base_struct, f));
+    let unique_constraints = unique_constraints.iter().map(|fields| {
+        let fields = fields.iter().map(column_name);
+        quote!(&[#(#fields),*])
+    });
+    let replace_conflict_columns = replace_conflict_fields.iter().map(column_name);
 
     quote! {
         impl<'a> silo::ToTable<'a> for #name {
             type Table = #table_name<'a>;
             const NAME: &'static str = #name_str_lit;
+            const IS_STRICT: bool = #is_strict;
+            const UNIQUE_CONSTRAINTS: &'static [&'static [&'static str]] = &[#(#unique_constraints),*];
+            const IS_SOFT_DELETE: bool = #is_soft_delete;
+            const REPLACE_CONFLICT_COLUMNS: &'static [&'static str] = &[#(#replace_conflict_columns),*];
         }
+
+        #(#single_column_assertions)*
+    }
+}
+
+/// The name of the column storing `field`, if it is stored in a single one.
+fn column_name(field: &syn::Ident) -> LitStr {
+    LitStr::new(&field.unraw().to_string(), field.span())
+}
+
+/// Fails to compile at `field`, if it is stored in several columns, like a
+/// nested struct. These are named after their own fields, so `column_name`
+/// would name no column.
+fn single_column_assertion(
+    base_struct: &super::base_struct::StructData,
+    field: &syn::Ident,
+) -> proc_macro2::TokenStream {
+    let Some(type_) = base_struct
+        .fields()
+        .into_iter()
+        .find(|f| f.name == field)
+        .map(|f| f.type_)
+    else {
+        return quote!();
+    };
+    let message = format!(
+        "{} is stored in several columns, so it cannot be part of a constraint.",
+        field.unraw()
+    );
+    quote_spanned! {field.span()=>
+        const _: () = assert!(<#type_ as silo::AsColumns>::COLUMN_COUNT == 1, #message);
     }
 }
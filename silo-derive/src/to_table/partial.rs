@@ -9,6 +9,8 @@ pub(crate) fn create_partial_for(
     let visibility = &base_struct.visibility;
     let name = &base_struct.name;
     let partial_name = base_struct.partial_name();
+    let generics = &base_struct.generics;
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
     let partial_type = create_partial_type_for(base_struct);
     // let variant_field = base_struct.variant_field().map(|f| f.name).into_iter();
     let field_names: Vec<_> = base_struct.fields().into_iter().map(|f| f.name).collect();
@@ -36,20 +38,46 @@ pub(crate) fn create_partial_for(
             })
         });
 
+    let field_docs: Vec<_> = base_struct
+        .fields()
+        .into_iter()
+        .chain(base_struct.variant_field())
+        .map(|f| f.docs_or(&format!("The value of `{}`, if it is set.", f.name.unraw())))
+        .collect();
+    let docs = base_struct.docs_with_summary(&format!(
+        "The fields of `{name}`, each of which may be missing, e.g. to update only some of them."
+    ));
+
+    let all_field_names = base_struct
+        .fields()
+        .into_iter()
+        .chain(base_struct.variant_field())
+        .map(|f| f.name);
+
     let into = create_into_for(base_struct);
     tokens.extend(quote! {
-        #[derive(Default)]
-        #visibility struct #partial_name {
-            #(#visibility #fields,)*
+        #docs
+        #visibility struct #partial_name #generics #where_clause {
+            #(#field_docs #visibility #fields,)*
+        }
+
+        // Derived, it would require every type parameter to implement
+        // Default, instead of only the partial types of the fields.
+        impl #impl_generics Default for #partial_name #type_generics #where_clause {
+            fn default() -> Self {
+                Self {
+                    #(#all_field_names: Default::default(),)*
+                }
+            }
         }
 
         #partial_type
 
-        impl silo::partial::HasPartial for #name {
-            type Partial = #partial_name;
+        impl #impl_generics silo::partial::HasPartial for #name #type_generics #where_clause {
+            type Partial = #partial_name #type_generics;
         }
 
-        impl silo::AsColumnsOptional for #partial_name {
+        impl #impl_generics silo::AsColumnsOptional for #partial_name #type_generics #where_clause {
             fn columns_skip_optional(
         &self,
         parent: Option<&str>,
@@ -63,7 +91,7 @@ pub(crate) fn create_partial_for(
     }
         }
 
-        impl silo::AsParamsOptional for #partial_name {
+        impl #impl_generics silo::AsParamsOptional for #partial_name #type_generics #where_clause {
             fn as_params_skip_optional<'b>(&'b self) -> Vec<silo::ToSqlDyn<'b>> {
                 let mut result = Vec::new();
                 #(result.append(&mut self.#field_names.as_params_skip_optional());)*
@@ -104,6 +132,7 @@ pub(crate) fn create_partial_for(
 fn create_into_for(base_struct: &super::base_struct::StructData) -> TokenStream {
     let name = &base_struct.name;
     let partial_name = base_struct.partial_name();
+    let (impl_generics, type_generics, where_clause) = base_struct.generics.split_for_impl();
     let field_names: Vec<_> = base_struct.fields().into_iter().map(|f| f.name).collect();
     let field_names_prefixed_with_optional: Vec<_> = base_struct
         .fields()
@@ -128,8 +157,8 @@ fn create_into_for(base_struct: &super::base_struct::StructData) -> TokenStream
             })
             .collect::<Vec<_>>();
         quote! {
-            impl Into<#partial_name> for #name {
-                fn into(self) -> #partial_name {
+            impl #impl_generics Into<#partial_name #type_generics> for #name #type_generics #where_clause {
+                fn into(self) -> #partial_name #type_generics {
                     use silo::EnumHelper;
                     #(
                         #[allow(non_snake_case)]
@@ -148,11 +177,15 @@ fn create_into_for(base_struct: &super::base_struct::StructData) -> TokenStream
             }
         }
     } else {
+        let field_values = base_struct.fields().into_iter().map(|f| {
+            let name = f.name;
+            f.wrap_storage(quote!(self.#name))
+        });
         quote! {
-            impl Into<#partial_name> for #name {
-                fn into(self) -> #partial_name {
+            impl #impl_generics Into<#partial_name #type_generics> for #name #type_generics #where_clause {
+                fn into(self) -> #partial_name #type_generics {
                     #partial_name {
-                        #(#field_names: self.#field_names.into(),)*
+                        #(#field_names: #field_values.into(),)*
                     }
                 }
             }
@@ -165,12 +198,13 @@ fn create_partial_type_for(
 ) -> proc_macro2::TokenStream {
     let name = &base_struct.name;
     let partial_name = base_struct.partial_name();
+    let (impl_generics, type_generics, where_clause) = base_struct.generics.split_for_impl();
     if let Some(variant_field) = base_struct.variant_field().map(|f| f.name) {
         quote! {
-            impl silo::partial::PartialType<#name> for #partial_name {
-                fn transpose(self) -> Option<#name> {
+            impl #impl_generics silo::partial::PartialType<#name #type_generics> for #partial_name #type_generics #where_clause {
+                fn transpose(self) -> Option<#name #type_generics> {
                     use silo::partial::PartialType;
-                    let #variant_field = self.#variant_field.transpose()?;
+                    let #variant_field = PartialType::transpose(self.#variant_field)?;
                     match #variant_field {
                         _ => None
                     }
@@ -179,12 +213,29 @@ fn create_partial_type_for(
         }
     } else {
         let field_names: Vec<_> = base_struct.fields().into_iter().map(|f| f.name).collect();
+        let field_values = base_struct.fields().into_iter().map(|f| {
+            let name = f.name;
+            match f.default {
+                // The field is never read, so its partial value is always missing.
+                _ if f.is_skip_select => {
+                    f.unwrap_storage(quote!(PartialType::transpose(self.#name).unwrap_or_default()))
+                }
+                Some(default) => {
+                    let default = &default.expr;
+                    f.unwrap_storage(
+                        quote!(PartialType::transpose(self.#name).or_else(|| Some(#default))?),
+                    )
+                }
+                None => f.unwrap_storage(quote!(PartialType::transpose(self.#name)?)),
+            }
+        });
         let skipped_field_names = base_struct.skipped_fields().into_iter().map(|f| f.name);
         quote! {
-            impl silo::partial::PartialType<#name> for #partial_name {
-                fn transpose(self) -> Option<#name> {
+            impl #impl_generics silo::partial::PartialType<#name #type_generics> for #partial_name #type_generics #where_clause {
+                fn transpose(self) -> Option<#name #type_generics> {
                     use silo::partial::PartialType;
-                    #(let #field_names = self.#field_names.transpose()?;)*
+                    // Option::transpose would shadow the trait method for Result fields.
+                    #(let #field_names = #field_values;)*
                     Some(#name {
                         #(#field_names,)*
                         #(#skipped_field_names: Default::default(),)*
@@ -1,31 +1,36 @@
+#[cfg(feature = "time")]
 use time::{format_description::FormatItem, macros::format_description};
 
 pub trait ToSqlValueString {
     fn to_sql_value_string(self) -> String;
 }
 
+#[cfg(feature = "uuid")]
 impl ToSqlValueString for uuid::Uuid {
     fn to_sql_value_string(self) -> String {
         self.to_string()
     }
 }
 
+#[cfg(feature = "uuid")]
 impl ToSqlValueString for uuid::NonNilUuid {
     fn to_sql_value_string(self) -> String {
         self.get().to_string()
     }
 }
 
+// Values are stored by the ToSql implementations of rusqlite, so filters have
+// to use exactly the same encodings.
+#[cfg(feature = "time")]
 impl ToSqlValueString for time::Time {
     fn to_sql_value_string(self) -> String {
-        const TIME_FORMAT: &[FormatItem<'_>] = format_description!(
-            version = 2,
-            "[hour]:[minute][optional [:[second][optional [.[subsecond]]]]]"
-        );
-        self.format(&TIME_FORMAT).unwrap()
+        const TIME_ENCODING: &[FormatItem<'_>] =
+            format_description!(version = 2, "[hour]:[minute]:[second].[subsecond]");
+        self.format(&TIME_ENCODING).unwrap()
     }
 }
 
+#[cfg(feature = "time")]
 impl ToSqlValueString for time::Date {
     fn to_sql_value_string(self) -> String {
         const DATE_FORMAT: &[FormatItem<'_>] =
@@ -34,6 +39,7 @@ impl ToSqlValueString for time::Date {
     }
 }
 
+#[cfg(feature = "time")]
 impl ToSqlValueString for time::OffsetDateTime {
     fn to_sql_value_string(self) -> String {
         const OFFSET_DATE_TIME_ENCODING: &[FormatItem<'_>] = format_description!(
@@ -44,8 +50,61 @@ impl ToSqlValueString for time::OffsetDateTime {
     }
 }
 
+#[cfg(feature = "chrono")]
 impl ToSqlValueString for chrono::DateTime<chrono::Utc> {
     fn to_sql_value_string(self) -> String {
         self.to_rfc3339()
     }
 }
+
+// These use the same formats as the ToSql implementations of rusqlite.
+#[cfg(feature = "chrono")]
+impl ToSqlValueString for chrono::NaiveDate {
+    fn to_sql_value_string(self) -> String {
+        self.format("%F").to_string()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl ToSqlValueString for chrono::NaiveTime {
+    fn to_sql_value_string(self) -> String {
+        self.format("%T%.f").to_string()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl ToSqlValueString for chrono::NaiveDateTime {
+    fn to_sql_value_string(self) -> String {
+        self.format("%F %T%.f").to_string()
+    }
+}
+
+impl ToSqlValueString for std::net::IpAddr {
+    fn to_sql_value_string(self) -> String {
+        self.to_string()
+    }
+}
+
+impl ToSqlValueString for std::net::Ipv4Addr {
+    fn to_sql_value_string(self) -> String {
+        self.to_string()
+    }
+}
+
+impl ToSqlValueString for std::net::Ipv6Addr {
+    fn to_sql_value_string(self) -> String {
+        self.to_string()
+    }
+}
+
+impl ToSqlValueString for std::path::PathBuf {
+    fn to_sql_value_string(self) -> String {
+        self.to_string_lossy().into_owned()
+    }
+}
+
+impl ToSqlValueString for std::ffi::OsString {
+    fn to_sql_value_string(self) -> String {
+        self.to_string_lossy().into_owned()
+    }
+}
@@ -1,15 +1,36 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, path::PathBuf};
+
+use crate::schema::SchemaMismatch;
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("{0}")]
-    Rusqlite(#[from] rusqlite::Error),
+    Rusqlite(#[source] rusqlite::Error),
+    #[cfg(feature = "serde")]
+    #[error("Value cannot be serialized to json: {0}")]
+    Json(#[source] serde_json::Error),
     #[error("No column named {0} could be found.")]
     MissingColumn(Cow<'static, str>),
+    #[error("No table named {0} could be found.")]
+    MissingTable(Cow<'static, str>),
+    #[error("Upserting into {0} needs conflict columns, since it has no primary key.")]
+    MissingConflictColumns(Cow<'static, str>),
+    #[error("A row of {0} has a column, which is NULL or could not be read.")]
+    IncompleteRow(Cow<'static, str>),
+    #[error("The schema of the database does not match: {0:?}")]
+    SchemaMismatch(Vec<SchemaMismatch>),
     #[error("Value has type {1}, which could not be converted to {0}.")]
     WrongColumnType(Cow<'static, str>, rusqlite::types::Type),
     #[error("Could not migrate value because of this: {0}.")]
     CouldNotMigrate(Cow<'static, str>),
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+    #[error("The file {0} already exists.")]
+    TargetExists(PathBuf),
+    #[error(
+        "The statement binds {0} parameters, but at most {1} can be bound and it cannot be split."
+    )]
+    TooManyParameters(usize, usize),
     #[error("Todo: {0}")]
     Todo(String),
     #[error("IllFormattedColumn: {1} cannot be parsed into {0}: {2:?}")]
@@ -19,3 +40,21 @@ pub enum Error {
         Option<Box<dyn std::error::Error>>,
     ),
 }
+
+impl From<rusqlite::Error> for Error {
+    fn from(error: rusqlite::Error) -> Self {
+        // A json parameter, which could not be serialized, fails while it is
+        // bound, see `json::as_params`.
+        #[cfg(feature = "serde")]
+        let error = match error {
+            rusqlite::Error::ToSqlConversionFailure(err) => {
+                match err.downcast::<serde_json::Error>() {
+                    Ok(err) => return Self::Json(*err),
+                    Err(err) => rusqlite::Error::ToSqlConversionFailure(err),
+                }
+            }
+            error => error,
+        };
+        Self::Rusqlite(error)
+    }
+}
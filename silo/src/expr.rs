@@ -0,0 +1,92 @@
+use std::{
+    borrow::Cow,
+    fmt::Write,
+    ops::{Add, Div, Mul, Sub},
+};
+
+use rusqlite::types::Value;
+
+/// A small arithmetic expression, which can be used to update a column
+/// relative to its own or other columns of the same row.
+///
+/// ```rust
+///# use silo::expr::Expr;
+/// let discounted = Expr::column("price") * Expr::literal(0.9);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Column(Cow<'static, str>),
+    /// Literals are bound as parameters.
+    Literal(Value),
+    Add(Box<Expr>, Box<Expr>),
+    Sub(Box<Expr>, Box<Expr>),
+    Mul(Box<Expr>, Box<Expr>),
+    Div(Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    pub fn column(name: impl Into<Cow<'static, str>>) -> Self {
+        Self::Column(name.into())
+    }
+
+    pub fn literal(value: impl Into<Value>) -> Self {
+        Self::Literal(value.into())
+    }
+
+    pub(crate) fn columns(&self) -> Vec<&str> {
+        match self {
+            Expr::Column(name) => vec![name],
+            Expr::Literal(_) => Vec::new(),
+            Expr::Add(lhs, rhs)
+            | Expr::Sub(lhs, rhs)
+            | Expr::Mul(lhs, rhs)
+            | Expr::Div(lhs, rhs) => {
+                let mut result = lhs.columns();
+                result.extend(rhs.columns());
+                result
+            }
+        }
+    }
+
+    /// Writes the expression with anonymous parameters for the literals, which
+    /// are returned in the order of appearance.
+    pub(crate) fn to_sql<'a>(&'a self, sql: &mut String, params: &mut Vec<&'a Value>) {
+        let (lhs, operator, rhs) = match self {
+            Expr::Column(name) => {
+                _ = write!(sql, "\"{name}\"");
+                return;
+            }
+            Expr::Literal(value) => {
+                sql.push('?');
+                params.push(value);
+                return;
+            }
+            Expr::Add(lhs, rhs) => (lhs, '+', rhs),
+            Expr::Sub(lhs, rhs) => (lhs, '-', rhs),
+            Expr::Mul(lhs, rhs) => (lhs, '*', rhs),
+            Expr::Div(lhs, rhs) => (lhs, '/', rhs),
+        };
+        sql.push('(');
+        lhs.to_sql(sql, params);
+        _ = write!(sql, " {operator} ");
+        rhs.to_sql(sql, params);
+        sql.push(')');
+    }
+}
+
+macro_rules! impl_operator {
+    ($trait:ident, $fn:ident) => {
+        impl $trait for Expr {
+            type Output = Expr;
+
+            fn $fn(self, rhs: Self) -> Self::Output {
+                Expr::$trait(Box::new(self), Box::new(rhs))
+            }
+        }
+    };
+}
+
+impl_operator!(Add, add);
+impl_operator!(Sub, sub);
+impl_operator!(Mul, mul);
+impl_operator!(Div, div);
@@ -1,9 +1,8 @@
-use crate::{AsParams, ToSqlDyn, conversions::ToSqlValueString};
-use chrono::{DateTime, Utc};
+use crate::conversions::ToSqlValueString;
+use crate::{AsParams, Int128, ToSqlDyn};
 use std::fmt::Write;
-use uuid::{NonNilUuid, Uuid};
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub enum OptionalFilter<T: Filter> {
     #[default]
     IsEither,
@@ -32,25 +31,94 @@ impl<T: Filter> Filter for OptionalFilter<T> {
             OptionalFilter::IsSomeAnd(it) => it.to_sql(sql, parent),
         }
     }
+
+    fn split_in_lists(self, max_params: usize) -> Vec<Self> {
+        match self {
+            OptionalFilter::IsSomeAnd(it) => it
+                .split_in_lists(max_params)
+                .into_iter()
+                .map(OptionalFilter::IsSomeAnd)
+                .collect(),
+            it => vec![it],
+        }
+    }
+}
+
+#[derive(Default, Clone)]
+pub enum ResultFilter<T: Filter, E: Filter> {
+    #[default]
+    IsEither,
+    IsOk,
+    IsErr,
+    IsOkAnd(T),
+    IsErrAnd(E),
+}
+
+impl<T: Filter, E: Filter> AsParams for ResultFilter<T, E> {
+    fn as_params<'b>(&'b self) -> Vec<ToSqlDyn<'b>> {
+        match self {
+            ResultFilter::IsEither | ResultFilter::IsOk | ResultFilter::IsErr => Vec::new(),
+            ResultFilter::IsOkAnd(it) => it.as_params(),
+            ResultFilter::IsErrAnd(it) => it.as_params(),
+        }
+    }
+}
+
+impl<T: Filter, E: Filter> Filter for ResultFilter<T, E> {
+    fn to_sql(&self, sql: &mut String, parent: Option<&str>) {
+        let parent = parent.expect("Needs a column name for comparison.");
+        match self {
+            ResultFilter::IsEither => {}
+            ResultFilter::IsOk => {
+                ensure_where_or_and(sql);
+                _ = write!(sql, "{parent} = 'Ok'");
+            }
+            ResultFilter::IsErr => {
+                ensure_where_or_and(sql);
+                _ = write!(sql, "{parent} = 'Err'");
+            }
+            ResultFilter::IsOkAnd(it) => {
+                ensure_where_or_and(sql);
+                _ = write!(sql, "{parent} = 'Ok'");
+                it.to_sql(sql, Some(&format!("{parent}_ok")));
+            }
+            ResultFilter::IsErrAnd(it) => {
+                ensure_where_or_and(sql);
+                _ = write!(sql, "{parent} = 'Err'");
+                it.to_sql(sql, Some(&format!("{parent}_err")));
+            }
+        }
+    }
+
+    fn split_in_lists(self, max_params: usize) -> Vec<Self> {
+        match self {
+            ResultFilter::IsOkAnd(it) => it
+                .split_in_lists(max_params)
+                .into_iter()
+                .map(ResultFilter::IsOkAnd)
+                .collect(),
+            ResultFilter::IsErrAnd(it) => it
+                .split_in_lists(max_params)
+                .into_iter()
+                .map(ResultFilter::IsErrAnd)
+                .collect(),
+            it => vec![it],
+        }
+    }
 }
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub enum FieldFilter<T: IsFieldFilter> {
     #[default]
     None,
     Not(Box<FieldFilter<T>>),
     Comparison(T, ComparisonOperator),
+    /// The values are bound as parameters. Lists, which are longer than the
+    /// variable limit of the connection, are split into multiple queries.
+    In(Vec<T>),
 }
 
 impl<T: IsFieldFilter> FieldFilter<T> {
-    pub fn contains_not(t: &T) -> Self {
-        Self::not(Self::contains(t))
-    }
-
-    pub fn contains(t: &T) -> Self {
-        Self::Comparison(t.clone(), ComparisonOperator::Like)
-    }
-
     pub fn equals(t: impl Into<T>) -> Self {
         Self::Comparison(t.into(), ComparisonOperator::Equals)
     }
@@ -71,9 +139,90 @@ impl<T: IsFieldFilter> FieldFilter<T> {
         Self::Comparison(t.into(), ComparisonOperator::LessThanEquals)
     }
 
+    pub fn is_one_of(values: impl IntoIterator<Item = impl Into<T>>) -> Self {
+        Self::In(values.into_iter().map(Into::into).collect())
+    }
+
+    #[allow(clippy::should_implement_trait)]
     pub fn not(f: FieldFilter<T>) -> Self {
         Self::Not(Box::new(f))
     }
+
+    /// Returns true, if this filter does not restrict the rows at all.
+    /// Negating such a filter does not restrict them either.
+    pub fn is_ignored(&self) -> bool {
+        match self {
+            FieldFilter::None => true,
+            FieldFilter::Not(field_filter) => field_filter.is_ignored(),
+            FieldFilter::Comparison(..) | FieldFilter::In(_) => false,
+        }
+    }
+}
+
+/// A filter value, which is stored as text, so it can match parts of the
+/// stored value.
+pub trait IsTextFilter: IsFieldFilter {}
+
+impl IsTextFilter for String {}
+
+impl<T: IsTextFilter> FieldFilter<T> {
+    pub fn contains_not(t: &T) -> Self {
+        Self::not(Self::contains(t))
+    }
+
+    /// Matches text containing `t`, ignoring the case of ASCII letters.
+    /// Numbers have no parts to match, so they are compared with
+    /// [`FieldFilter::equals`] instead.
+    ///
+    /// ```rust,compile_fail
+    ///# use silo::filter::FieldFilter;
+    /// let filter = FieldFilter::<u32>::contains(&5);
+    /// ```
+    pub fn contains(t: &T) -> Self {
+        Self::Comparison(t.clone(), ComparisonOperator::Like)
+    }
+}
+
+impl FieldFilter<String> {
+    /// Matches text starting with `prefix`. `%` and `_` in the prefix match
+    /// themselves.
+    pub fn starts_with(prefix: impl Into<String>) -> Self {
+        Self::Comparison(prefix.into(), ComparisonOperator::StartsWith)
+    }
+
+    /// Matches text ending with `suffix`. `%` and `_` in the suffix match
+    /// themselves.
+    pub fn ends_with(suffix: impl Into<String>) -> Self {
+        Self::Comparison(suffix.into(), ComparisonOperator::EndsWith)
+    }
+
+    /// Matches text against a shell-style pattern, where `*` matches any
+    /// text, `?` matches a single character and `[...]` matches a set of
+    /// characters. Unlike [`FieldFilter::contains`], which uses LIKE, GLOB is
+    /// case-sensitive.
+    pub fn glob(pattern: impl Into<String>) -> Self {
+        Self::Comparison(pattern.into(), ComparisonOperator::Glob)
+    }
+
+    /// Matches text against a regular expression. SQLite does not define
+    /// REGEXP itself, so this needs the `regex` feature, which registers it
+    /// for every [`crate::Database`].
+    #[cfg(feature = "regex")]
+    pub fn matches(pattern: impl Into<String>) -> Self {
+        Self::Comparison(pattern.into(), ComparisonOperator::Regex)
+    }
+}
+
+/// A filter, which can be inverted. Used by the generated `<field>_is_not`
+/// methods of derived filters.
+pub trait Negate {
+    fn negate(self) -> Self;
+}
+
+impl<T: IsFieldFilter> Negate for FieldFilter<T> {
+    fn negate(self) -> Self {
+        Self::not(self)
+    }
 }
 
 impl<T: IsFieldFilter> AsParams for FieldFilter<T> {
@@ -81,21 +230,30 @@ impl<T: IsFieldFilter> AsParams for FieldFilter<T> {
         match self {
             FieldFilter::None => Vec::new(),
             FieldFilter::Not(field_filter) => field_filter.as_params(),
-            FieldFilter::Comparison(it, _) => {
-                vec![ToSqlDyn::Borrowed(it)]
-            }
+            // Comparisons write their value directly into the sql.
+            FieldFilter::Comparison(..) => Vec::new(),
+            FieldFilter::In(values) => values.iter().flat_map(AsParams::as_params).collect(),
         }
     }
 }
 
-pub trait Filter: AsParams {
+pub trait Filter: AsParams + Clone {
     fn to_sql(&self, sql: &mut String, parent: Option<&str>);
+
+    /// Splits the IN lists of this filter into lists of at most `max_params`
+    /// values. Loading the rows of every returned filter yields the same rows
+    /// as loading the rows of this filter. Negated lists cannot be split.
+    fn split_in_lists(self, max_params: usize) -> Vec<Self> {
+        let _ = max_params;
+        vec![self]
+    }
 }
 
 impl<T: IsFieldFilter> Filter for FieldFilter<T> {
     fn to_sql(&self, sql: &mut String, parent: Option<&str>) {
         match self {
             FieldFilter::None => {}
+            FieldFilter::Not(field_filter) if field_filter.is_ignored() => {}
             FieldFilter::Not(field_filter) => {
                 ensure_where_or_and(sql);
                 _ = write!(sql, "NOT (");
@@ -111,8 +269,75 @@ impl<T: IsFieldFilter> Filter for FieldFilter<T> {
                     parent.expect("Needs a column name for comparison."),
                 );
             }
+            FieldFilter::In(values) => {
+                ensure_where_or_and(sql);
+                let parent = parent.expect("Needs a column name for comparison.");
+                _ = write!(sql, "{parent} IN (");
+                for i in 0..values.len() {
+                    if i > 0 {
+                        sql.push_str(", ");
+                    }
+                    sql.push('?');
+                }
+                sql.push(')');
+            }
         }
     }
+
+    fn split_in_lists(self, max_params: usize) -> Vec<Self> {
+        match self {
+            FieldFilter::In(values) if values.len() > max_params => values
+                .chunks(max_params.max(1))
+                .map(|chunk| FieldFilter::In(chunk.to_vec()))
+                .collect(),
+            it => vec![it],
+        }
+    }
+}
+
+/// Writes `(first) OR (alternative) OR ...`, where `first` writes the
+/// conditions of the filter itself and every alternative writes its own.
+/// Used by derived filters.
+#[doc(hidden)]
+pub fn write_alternatives<A: FnOnce(&mut String)>(
+    sql: &mut String,
+    first: impl FnOnce(&mut String),
+    alternatives: impl IntoIterator<Item = A>,
+) {
+    ensure_where_or_and(sql);
+    sql.push_str("((");
+    first(sql);
+    close_alternative(sql);
+    for alternative in alternatives {
+        sql.push_str(" OR (");
+        alternative(sql);
+        close_alternative(sql);
+    }
+    sql.push(')');
+}
+
+/// Writes a condition, which no row matches. Used by derived filters.
+#[doc(hidden)]
+pub fn write_matches_nothing(sql: &mut String) {
+    ensure_where_or_and(sql);
+    sql.push('0');
+}
+
+/// Writes a condition, which only matches rows of a `#[silo(soft_delete)]`
+/// table, which are not deleted. Used by derived filters, once for the whole
+/// filter of a table.
+#[doc(hidden)]
+pub fn write_not_deleted(sql: &mut String) {
+    ensure_where_or_and(sql);
+    _ = write!(sql, "\"{}\" IS NULL", crate::SOFT_DELETE_COLUMN);
+}
+
+fn close_alternative(sql: &mut String) {
+    // An alternative without any conditions matches every row.
+    if sql.ends_with('(') {
+        sql.push('1');
+    }
+    sql.push(')');
 }
 
 fn ensure_where_or_and(sql: &mut String) {
@@ -130,6 +355,11 @@ pub trait Filterable {
     fn convert_to_equals_filter(self) -> Self::Filter;
 }
 
+/// A type, whose filter can match any of a list of values.
+pub trait InFilterable: Filterable + Sized {
+    fn convert_to_in_filter(values: Vec<Self>) -> Self::Filter;
+}
+
 impl<T: Filterable> Filterable for Option<T> {
     type Filter = OptionalFilter<T::Filter>;
 
@@ -141,6 +371,17 @@ impl<T: Filterable> Filterable for Option<T> {
     }
 }
 
+impl<T: Filterable, E: Filterable> Filterable for Result<T, E> {
+    type Filter = ResultFilter<T::Filter, E::Filter>;
+
+    fn convert_to_equals_filter(self) -> Self::Filter {
+        match self {
+            Ok(it) => ResultFilter::IsOkAnd(it.convert_to_equals_filter()),
+            Err(err) => ResultFilter::IsErrAnd(err.convert_to_equals_filter()),
+        }
+    }
+}
+
 macro_rules! impl_filterable {
     ($t:ty) => {
         impl Filterable for $t {
@@ -150,6 +391,12 @@ macro_rules! impl_filterable {
             }
         }
 
+        impl InFilterable for $t {
+            fn convert_to_in_filter(values: Vec<Self>) -> Self::Filter {
+                FieldFilter::In(values)
+            }
+        }
+
         impl IsFieldFilter for $t {
             fn to_sql(&self, sql: &mut String, operator: ComparisonOperator, parent: &str) {
                 _ = write!(sql, "{parent} {operator} ");
@@ -164,13 +411,108 @@ macro_rules! impl_filterable {
                 FieldFilter::equals(self.to_sql_value_string())
             }
         }
+
+        impl InFilterable for $t {
+            fn convert_to_in_filter(values: Vec<Self>) -> Self::Filter {
+                FieldFilter::In(
+                    values
+                        .into_iter()
+                        .map(|v| v.to_sql_value_string())
+                        .collect(),
+                )
+            }
+        }
     };
 }
 
-impl_filterable!(DateTime<Utc>, String);
-impl_filterable!(NonNilUuid, String);
-impl_filterable!(Uuid, String);
+macro_rules! impl_filterable_int128 {
+    ($t:ty) => {
+        impl Filterable for $t {
+            type Filter = FieldFilter<Vec<u8>>;
+            fn convert_to_equals_filter(self) -> Self::Filter {
+                FieldFilter::equals(self.to_blob().to_vec())
+            }
+        }
+
+        impl InFilterable for $t {
+            fn convert_to_in_filter(values: Vec<Self>) -> Self::Filter {
+                FieldFilter::In(values.into_iter().map(|v| v.to_blob().to_vec()).collect())
+            }
+        }
+    };
+}
+
+impl Filterable for char {
+    type Filter = FieldFilter<String>;
+    fn convert_to_equals_filter(self) -> Self::Filter {
+        FieldFilter::equals(self.to_string())
+    }
+}
+
+impl InFilterable for char {
+    fn convert_to_in_filter(values: Vec<Self>) -> Self::Filter {
+        FieldFilter::In(values.into_iter().map(String::from).collect())
+    }
+}
+
+impl Filterable for std::borrow::Cow<'static, str> {
+    type Filter = FieldFilter<String>;
+    fn convert_to_equals_filter(self) -> Self::Filter {
+        FieldFilter::equals(self.into_owned())
+    }
+}
+
+impl InFilterable for std::borrow::Cow<'static, str> {
+    fn convert_to_in_filter(values: Vec<Self>) -> Self::Filter {
+        FieldFilter::In(
+            values
+                .into_iter()
+                .map(std::borrow::Cow::into_owned)
+                .collect(),
+        )
+    }
+}
+
+impl Filterable for std::time::Duration {
+    type Filter = FieldFilter<i64>;
+    fn convert_to_equals_filter(self) -> Self::Filter {
+        FieldFilter::equals(crate::duration_as_nanos(&self))
+    }
+}
+
+impl InFilterable for std::time::Duration {
+    fn convert_to_in_filter(values: Vec<Self>) -> Self::Filter {
+        FieldFilter::In(values.iter().map(crate::duration_as_nanos).collect())
+    }
+}
+
+impl_filterable_int128!(u128);
+impl_filterable_int128!(i128);
+#[cfg(feature = "chrono")]
+impl_filterable!(chrono::DateTime<chrono::Utc>, String);
+#[cfg(feature = "chrono")]
+impl_filterable!(chrono::NaiveDate, String);
+#[cfg(feature = "chrono")]
+impl_filterable!(chrono::NaiveTime, String);
+#[cfg(feature = "chrono")]
+impl_filterable!(chrono::NaiveDateTime, String);
+#[cfg(feature = "time")]
+impl_filterable!(time::Time, String);
+#[cfg(feature = "time")]
+impl_filterable!(time::Date, String);
+#[cfg(feature = "time")]
+impl_filterable!(time::OffsetDateTime, String);
+#[cfg(feature = "uuid")]
+impl_filterable!(uuid::NonNilUuid, String);
+#[cfg(feature = "uuid")]
+impl_filterable!(uuid::Uuid, String);
+impl_filterable!(std::net::IpAddr, String);
+impl_filterable!(std::net::Ipv4Addr, String);
+impl_filterable!(std::net::Ipv6Addr, String);
+impl_filterable!(std::path::PathBuf, String);
+impl_filterable!(std::ffi::OsString, String);
 impl_filterable!(String);
+impl_filterable!(Vec<u8>);
 impl_filterable!(bool);
 impl_filterable!(u8);
 impl_filterable!(u16);
@@ -182,10 +524,49 @@ impl_filterable!(i16);
 impl_filterable!(i32);
 impl_filterable!(i64);
 impl_filterable!(isize);
-// We might wanna use a manual implementation later, to be closer to sqlite? To
-// be able to work around floating point shenanigans.
-impl_filterable!(f32);
-impl_filterable!(f64);
+
+/// SQLite stores NaN as NULL, so filters for NaN check for NULL instead of
+/// comparing with NaN, which never matches.
+macro_rules! impl_filterable_float {
+    ($t:ty) => {
+        impl Filterable for $t {
+            type Filter = FieldFilter<$t>;
+            fn convert_to_equals_filter(self) -> Self::Filter {
+                FieldFilter::equals(self)
+            }
+        }
+
+        impl InFilterable for $t {
+            fn convert_to_in_filter(values: Vec<Self>) -> Self::Filter {
+                FieldFilter::In(values)
+            }
+        }
+
+        impl IsFieldFilter for $t {
+            fn to_sql(&self, sql: &mut String, operator: ComparisonOperator, parent: &str) {
+                if !self.is_nan() {
+                    _ = write!(sql, "{parent} {operator} ");
+                    self.write_to_sql(sql, operator);
+                } else if operator == ComparisonOperator::Equals {
+                    _ = write!(sql, "{parent} IS NULL");
+                } else {
+                    // Like every comparison with NaN, this never matches.
+                    _ = write!(sql, "{parent} {operator} NULL");
+                }
+            }
+        }
+
+        impl FieldFilter<$t> {
+            /// Matches rows where the value is NaN, which is stored as NULL.
+            pub fn is_nan() -> Self {
+                Self::equals(<$t>::NAN)
+            }
+        }
+    };
+}
+
+impl_filterable_float!(f32);
+impl_filterable_float!(f64);
 
 macro_rules! impl_write_to_sql_as_to_string {
     ($t:ty) => {
@@ -215,14 +596,43 @@ impl WriteToSql for bool {
         _ = write!(sql, "{}", *self as usize);
     }
 }
+impl WriteToSql for Vec<u8> {
+    fn write_to_sql(&self, sql: &mut String, _operator: ComparisonOperator) {
+        sql.push_str("X'");
+        for byte in self {
+            _ = write!(sql, "{byte:02X}");
+        }
+        sql.push('\'');
+    }
+}
+
 impl WriteToSql for String {
     fn write_to_sql(&self, sql: &mut String, operator: ComparisonOperator) {
-        let surroundings = match operator {
-            ComparisonOperator::Like => "%",
-            _ => "",
-        };
-        _ = write!(sql, "'{surroundings}{self}{surroundings}'");
+        let escaped = self.replace('\'', "''");
+        match operator {
+            ComparisonOperator::Like => _ = write!(sql, "'%{escaped}%'"),
+            // The wildcards of LIKE are escaped, so that only the prefix or
+            // suffix itself matches.
+            ComparisonOperator::StartsWith => {
+                _ = write!(sql, "'{}%' ESCAPE '\\'", escape_like(&escaped))
+            }
+            ComparisonOperator::EndsWith => {
+                _ = write!(sql, "'%{}' ESCAPE '\\'", escape_like(&escaped))
+            }
+            _ => _ = write!(sql, "'{escaped}'"),
+        }
+    }
+}
+
+fn escape_like(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            result.push('\\');
+        }
+        result.push(c);
     }
+    result
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display)]
@@ -239,12 +649,20 @@ pub enum ComparisonOperator {
     LessThanEquals,
     #[strum(to_string = "LIKE")]
     Like,
+    #[strum(to_string = "LIKE")]
+    StartsWith,
+    #[strum(to_string = "LIKE")]
+    EndsWith,
+    #[strum(to_string = "GLOB")]
+    Glob,
+    #[strum(to_string = "REGEXP")]
+    Regex,
 }
 
 pub trait WriteToSql {
     fn write_to_sql(&self, sql: &mut String, operator: ComparisonOperator);
 }
 
-pub trait IsFieldFilter: rusqlite::ToSql + Clone + WriteToSql {
+pub trait IsFieldFilter: rusqlite::ToSql + AsParams + Clone + WriteToSql {
     fn to_sql(&self, sql: &mut String, operator: ComparisonOperator, parent: &str);
 }
@@ -0,0 +1,91 @@
+use rusqlite::types::ToSqlOutput;
+use serde::{Serialize, de::DeserializeOwned, ser::Error as _};
+
+use crate::{
+    Error, ExtractFromRow, IsSingleColumn, SqlColumnType, ToSqlDyn, filter::FieldFilter,
+    filter::Filterable, filter::InFilterable, partial,
+};
+
+/// Stores the wrapped value as JSON text in a single column. Fields marked
+/// with `#[silo(json)]` are stored through this type.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Json<T>(pub T);
+
+/// Serializes the value into a single JSON text parameter. If the value cannot
+/// be serialized, e.g. a map with keys, which are not strings, binding the
+/// parameter fails with [`Error::Json`].
+pub fn as_params<T: Serialize>(value: &T) -> Vec<ToSqlDyn<'_>> {
+    vec![ToSqlDyn::Boxed(Box::new(Serialized(
+        serde_json::to_string(value),
+    )))]
+}
+
+/// A value, which was serialized before it is bound.
+struct Serialized(Result<String, serde_json::Error>);
+
+impl rusqlite::ToSql for Serialized {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        match &self.0 {
+            Ok(json) => json.to_sql(),
+            // The error is not Clone, but binding may be repeated.
+            Err(err) => Err(rusqlite::Error::ToSqlConversionFailure(Box::new(
+                serde_json::Error::custom(err),
+            ))),
+        }
+    }
+}
+
+impl<T> IsSingleColumn for Json<T> {
+    const SQL_COLUMN_TYPE: SqlColumnType = SqlColumnType::Text;
+}
+
+impl<T: Serialize> crate::AsParams for Json<T> {
+    fn as_params<'b>(&'b self) -> Vec<ToSqlDyn<'b>> {
+        as_params(&self.0)
+    }
+}
+
+impl<T: DeserializeOwned> ExtractFromRow for Json<T> {
+    fn try_from_row_simple(column_name: &str, row: &rusqlite::Row) -> Result<Self, Error> {
+        match row.get::<&str, String>(column_name) {
+            Ok(it) => Ok(Json(serde_json::from_str(&it).map_err(|e| {
+                Error::IllFormattedColumn("Json".into(), it, Some(Box::new(e)))
+            })?)),
+            Err(rusqlite::Error::InvalidColumnName(_)) => {
+                Err(Error::MissingColumn(column_name.to_string().into()))
+            }
+            Err(rusqlite::Error::InvalidColumnType(.., t)) => {
+                Err(Error::WrongColumnType("Json".into(), t))
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+impl<T> partial::HasPartial for Json<T> {
+    type Partial = Option<Json<T>>;
+}
+
+/// Json columns can only be compared by their serialized text. A value, which
+/// cannot be serialized, cannot be stored either, so it matches no row.
+impl<T: Serialize> Filterable for Json<T> {
+    type Filter = FieldFilter<String>;
+
+    fn convert_to_equals_filter(self) -> Self::Filter {
+        match serde_json::to_string(&self.0) {
+            Ok(json) => json.convert_to_equals_filter(),
+            Err(_) => FieldFilter::In(Vec::new()),
+        }
+    }
+}
+
+impl<T: Serialize> InFilterable for Json<T> {
+    fn convert_to_in_filter(values: Vec<Self>) -> Self::Filter {
+        FieldFilter::In(
+            values
+                .iter()
+                .filter_map(|v| serde_json::to_string(&v.0).ok())
+                .collect(),
+        )
+    }
+}
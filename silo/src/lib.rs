@@ -1,28 +1,66 @@
 use std::{
     borrow::Cow,
-    fmt::Debug,
+    cell::RefCell,
+    collections::HashMap,
+    fmt::{Debug, Display, Write},
+    hash::Hash,
     path::Path,
     sync::atomic::{AtomicBool, Ordering::SeqCst},
 };
 
-use chrono::{DateTime, Utc};
 pub use rusqlite;
-use rusqlite::{Connection, ErrorCode, Params, types::Null};
+use rusqlite::{Connection, ErrorCode, Params, limits::Limit, types::Null};
 
 mod error;
 pub mod partial;
 pub use error::Error;
 mod conversions;
+pub mod expr;
 pub mod filter;
+#[cfg(feature = "serde")]
+pub mod json;
+pub mod migration;
+pub mod nan;
 pub mod projections;
+pub mod schema;
 
+/// The derives forward the doc comments of a type onto the types generated
+/// for it and document their fields and methods, so they can be used in
+/// crates, which deny missing docs.
+///
+/// ```rust
+/// #![deny(missing_docs)]
+/// #![doc = "Movies and where to watch them."]
+/// use silo::derive::{ToColumns, ToTable};
+///
+/// /// An address of a cinema.
+/// #[derive(ToColumns)]
+/// pub struct Address {
+///     /// The city of the cinema.
+///     pub city: String,
+///     street: String,
+/// }
+///
+/// /// A movie, which is shown in a cinema.
+/// #[derive(ToTable)]
+/// #[silo(generate_insert_struct)]
+/// pub struct Movie {
+///     #[silo(primary)]
+///     id: i64,
+///     /// The title in the original language.
+///     pub title: String,
+///     rating: f64,
+///     cinema: Address,
+/// }
+///
+/// fn main() {}
+/// ```
 pub mod derive {
     pub use silo_derive::ToColumns;
     pub use silo_derive::ToTable;
 }
 
-use time::OffsetDateTime;
-use time::{Date, Time};
+#[cfg(feature = "uuid")]
 use uuid::{NonNilUuid, Uuid};
 
 use crate::projections::{Projectable, Projection, ProjectionColumns};
@@ -66,12 +104,32 @@ macro_rules! type_checker {
                     // The return type of core::unreachable!() is never type,
                     // which can be assigned to any type.
                     let unreachable_obj: $struct = core::unreachable!();
-                    let _: _ = unreachable_obj.$field.unwrap()$(.$more_fields.unwrap())*;
+                    let _ = unreachable_obj.$field.unwrap()$(.$more_fields.unwrap())*;
                 }
         };
     };
 }
 
+#[cfg(feature = "regex")]
+#[doc(hidden)]
+#[macro_export]
+/// Expands to the given items, because the `regex` feature of silo is enabled.
+/// The derives put the code, which needs it, in here, since a `cfg` in the
+/// generated code would check the features of the deriving crate instead.
+macro_rules! __with_regex {
+    ($($item:item)*) => {
+        $($item)*
+    };
+}
+
+#[cfg(not(feature = "regex"))]
+#[doc(hidden)]
+#[macro_export]
+/// Expands to nothing, because the `regex` feature of silo is disabled.
+macro_rules! __with_regex {
+    ($($item:item)*) => {};
+}
+
 /// This is used by column_name_of to support r#raw identifier correctly.
 pub const fn strip_leading_raw_identifier(ident: &str) -> &str {
     konst::string::trim_start_matches(ident, "r#")
@@ -164,8 +222,127 @@ impl ToSqlDyn<'static> {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SaveOptions {
+    /// Replace an existing file. Otherwise saving fails with
+    /// [`Error::TargetExists`].
+    pub overwrite: bool,
+    /// Before overwriting, move the existing file to
+    /// `{path}.{unix timestamp in milliseconds}.bak`.
+    pub backup_existing: bool,
+}
+
+impl Default for SaveOptions {
+    fn default() -> Self {
+        Self {
+            overwrite: true,
+            backup_existing: false,
+        }
+    }
+}
+
+/// Pragmas set by [`Database::open_with_pragmas`] before any table is
+/// created. Pragmas, which are not enabled, keep the default of SQLite.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Pragmas {
+    /// Sets `journal_mode=WAL`. In-memory databases ignore this.
+    pub wal: bool,
+    /// Sets `foreign_keys=ON`.
+    pub foreign_keys: bool,
+    /// Sets `synchronous=NORMAL`, which is safe to use together with `wal`.
+    pub synchronous_normal: bool,
+}
+
+impl Pragmas {
+    fn to_sql(self) -> String {
+        let mut sql = String::new();
+        if self.wal {
+            sql.push_str("PRAGMA journal_mode=WAL;");
+        }
+        if self.foreign_keys {
+            sql.push_str("PRAGMA foreign_keys=ON;");
+        }
+        if self.synchronous_normal {
+            sql.push_str("PRAGMA synchronous=NORMAL;");
+        }
+        sql
+    }
+}
+
+/// How [`Database::checkpoint`] copies the write-ahead log into the database
+/// file. See <https://sqlite.org/pragma.html#pragma_wal_checkpoint>.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CheckpointMode {
+    /// Copies as much as possible without waiting for other connections.
+    #[default]
+    Passive,
+    /// Waits for writers, then copies the whole log.
+    Full,
+    /// Like [`CheckpointMode::Full`], but also truncates the log file to
+    /// zero bytes afterwards.
+    Truncate,
+}
+
+impl CheckpointMode {
+    fn as_sql(self) -> &'static str {
+        match self {
+            CheckpointMode::Passive => "PASSIVE",
+            CheckpointMode::Full => "FULL",
+            CheckpointMode::Truncate => "TRUNCATE",
+        }
+    }
+}
+
 pub struct Database {
     connection: rusqlite::Connection,
+    registered_tables: RefCell<Vec<schema::RegisteredTable>>,
+}
+
+/// The name of a table, qualified with the attached database it is in, see
+/// [`Database::load_attached`]. It is written into sql in double quotes, like
+/// `"alias"."name"`, or `"name"` for a table of the main database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TableName<'n> {
+    /// The alias of the attached database, None for the main database.
+    pub schema: Option<&'n str>,
+    pub name: &'n str,
+}
+
+impl<'n> TableName<'n> {
+    /// Names the attached database in front of a table or index, nothing for
+    /// the main database.
+    pub(crate) fn schema_prefix(&self) -> String {
+        self.schema
+            .map(|s| format!("{}.", Quoted(s)))
+            .unwrap_or_default()
+    }
+
+    /// Names another object, like an index, of the same database.
+    pub(crate) fn sibling(&self, name: &str) -> String {
+        format!("{}{}", self.schema_prefix(), Quoted(name))
+    }
+}
+
+impl<'n> From<&'n str> for TableName<'n> {
+    fn from(name: &'n str) -> Self {
+        Self { schema: None, name }
+    }
+}
+
+impl Display for TableName<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.schema_prefix(), Quoted(self.name))
+    }
+}
+
+/// Writes a name in double quotes, so it may contain any character. Double
+/// quotes inside of it are doubled.
+pub(crate) struct Quoted<'a>(pub &'a str);
+
+impl Display for Quoted<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "\"{}\"", self.0.replace('"', "\"\""))
+    }
 }
 
 fn execute<P: Params>(
@@ -177,9 +354,39 @@ fn execute<P: Params>(
     connection.execute(sql, params)
 }
 
+/// Registers the `regexp` function, which SQLite calls for `X REGEXP Y`, but
+/// does not define itself. Compiled patterns are cached per statement.
+#[cfg(feature = "regex")]
+fn register_regexp(connection: &rusqlite::Connection) -> Result<(), rusqlite::Error> {
+    use rusqlite::functions::FunctionFlags;
+    connection.create_scalar_function(
+        "regexp",
+        2,
+        FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC,
+        |context| {
+            let regex: std::sync::Arc<regex::Regex> = context.get_or_create_aux(
+                0,
+                |pattern| -> Result<_, Box<dyn std::error::Error + Send + Sync>> {
+                    Ok(regex::Regex::new(pattern.as_str()?)?)
+                },
+            )?;
+            let text = context
+                .get_raw(1)
+                .as_str_or_null()
+                .map_err(|e| rusqlite::Error::UserFunctionError(e.into()))?;
+            Ok(text.is_some_and(|text| regex.is_match(text)))
+        },
+    )
+}
+
 impl Database {
-    fn new_from_connection(connection: rusqlite::Connection) -> Self {
-        Self { connection }
+    fn new_from_connection(connection: rusqlite::Connection) -> Result<Self, Error> {
+        #[cfg(feature = "regex")]
+        register_regexp(&connection)?;
+        Ok(Self {
+            connection,
+            registered_tables: RefCell::new(Vec::new()),
+        })
     }
 
     /// Calls rusqlite::Connection::from_handle.
@@ -191,66 +398,454 @@ impl Database {
     ///
     /// # Safety
     /// This function is unsafe because improper use may impact the Connection.
-    pub unsafe fn from_connection(
-        connection: &rusqlite::Connection,
-    ) -> Result<Self, rusqlite::Error> {
+    pub unsafe fn from_connection(connection: &rusqlite::Connection) -> Result<Self, Error> {
         let connection = unsafe { rusqlite::Connection::from_handle(connection.handle())? };
-        Ok(Self::new_from_connection(connection))
+        Self::new_from_connection(connection)
     }
 
-    pub fn create_in_memory() -> Result<Self, rusqlite::Error> {
+    pub fn create_in_memory() -> Result<Self, Error> {
         let connection = rusqlite::Connection::open_in_memory()?;
         execute(&connection, "DROP TABLE IF EXISTS temporary", ())?;
-        Ok(Self::new_from_connection(connection))
+        Self::new_from_connection(connection)
+    }
+
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let connection = rusqlite::Connection::open(path)?;
+        execute(&connection, "DROP TABLE IF EXISTS temporary", ())?;
+        Self::new_from_connection(connection)
+    }
+
+    /// Like [`Database::open`], but with custom flags, e.g. for a shared
+    /// cache. When opened read-only, loading a table never creates or
+    /// changes it.
+    pub fn open_with_flags(
+        path: impl AsRef<Path>,
+        flags: rusqlite::OpenFlags,
+    ) -> Result<Self, Error> {
+        let connection = rusqlite::Connection::open_with_flags(path, flags)?;
+        if !connection.is_readonly(rusqlite::MAIN_DB)? {
+            execute(&connection, "DROP TABLE IF EXISTS temporary", ())?;
+        }
+        Self::new_from_connection(connection)
     }
 
-    pub fn open(path: impl AsRef<Path>) -> Result<Self, rusqlite::Error> {
+    /// Like [`Database::open`], but sets the given pragmas first.
+    pub fn open_with_pragmas(path: impl AsRef<Path>, pragmas: Pragmas) -> Result<Self, Error> {
         let connection = rusqlite::Connection::open(path)?;
+        let sql = pragmas.to_sql();
+        debug_sql(&sql);
+        connection.execute_batch(&sql)?;
         execute(&connection, "DROP TABLE IF EXISTS temporary", ())?;
-        Ok(Self::new_from_connection(connection))
+        Self::new_from_connection(connection)
     }
 
-    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), rusqlite::Error> {
+    /// Opens an existing database read-only. Loading a table, which does not
+    /// exist, fails instead of creating it.
+    pub fn open_readonly(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Self::open_with_flags(
+            path,
+            rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY
+                | rusqlite::OpenFlags::SQLITE_OPEN_URI
+                | rusqlite::OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
         self.connection.backup("main", path, None)?;
         Ok(())
     }
-    pub fn load<'a, T: ToTable<'a>>(&'a self) -> rusqlite::Result<T::Table> {
-        self.create::<T>()?;
+
+    /// Like [`Database::save`], but lets you decide what happens with an
+    /// existing file at `path`.
+    pub fn save_with(&self, path: impl AsRef<Path>, options: SaveOptions) -> Result<(), Error> {
+        let path = path.as_ref();
+        if path.exists() {
+            if !options.overwrite {
+                return Err(Error::TargetExists(path.to_path_buf()));
+            }
+            if options.backup_existing {
+                let mut backup = path.as_os_str().to_owned();
+                let timestamp = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default();
+                backup.push(format!(".{}.bak", timestamp.as_millis()));
+                std::fs::rename(path, backup)?;
+            }
+        }
+        self.save(path)?;
+        Ok(())
+    }
+    /// The maximum number of parameters, which can be bound in a single
+    /// statement. Filters with longer IN lists are split into multiple
+    /// queries.
+    pub fn variable_limit(&self) -> Result<usize, Error> {
+        variable_limit(&self.connection)
+    }
+
+    /// Sets the maximum number of parameters, which can be bound in a single
+    /// statement and returns the previous limit. SQLite silently caps this at
+    /// the limit it was compiled with.
+    pub fn set_variable_limit(&self, limit: usize) -> Result<usize, Error> {
+        let limit = i32::try_from(limit).unwrap_or(i32::MAX);
+        let previous = self
+            .connection
+            .set_limit(Limit::SQLITE_LIMIT_VARIABLE_NUMBER, limit)?;
+        Ok(previous as usize)
+    }
+
+    /// Sets how many prepared statements are kept per connection. Queries
+    /// with the same sql reuse the prepared statement, the least recently used
+    /// ones are dropped first. A capacity of 0 disables the cache.
+    pub fn set_statement_cache_capacity(&self, capacity: usize) {
+        self.connection
+            .set_prepared_statement_cache_capacity(capacity);
+    }
+
+    pub fn load<'a, T: ToTable<'a>>(&'a self) -> Result<T::Table, Error> {
+        self.register::<T>();
+        self.create::<T>(T::NAME.into())?;
 
         Ok(T::Table::from_connection(&self.connection))
     }
 
-    fn create<'a, T: ToTable<'a>>(&'a self) -> Result<(), rusqlite::Error> {
-        if self.connection.table_exists(None, T::NAME)? {
+    /// Like [`Database::load`], but checks the schema of the table afterwards.
+    /// `load` creates a missing table and adds missing columns with a
+    /// default, but uses an existing table as is otherwise. `load_checked`
+    /// then fails with [`Error::SchemaMismatch`], if the table still differs
+    /// from the columns of `T`, see [`Database::pending_alterations`].
+    pub fn load_checked<'a, T: ToTable<'a>>(&'a self) -> Result<T::Table, Error> {
+        let table = self.load::<T>()?;
+        let mismatches = self.pending_alterations::<T>()?;
+        if !mismatches.is_empty() {
+            return Err(Error::SchemaMismatch(mismatches));
+        }
+        Ok(table)
+    }
+
+    /// Like [`Database::load`], but stores the rows of `T` in the table
+    /// `table_name`, e.g. to read an existing table with a different name.
+    /// The table is created if it is missing. It is not registered, so
+    /// [`Database::validate_schema`] does not check it.
+    pub fn load_as<'a, T: ToTable<'a>>(&'a self, table_name: &'a str) -> Result<T::Table, Error> {
+        self.create::<T>(table_name.into())?;
+
+        Ok(T::Table::from_connection_with_name(
+            &self.connection,
+            table_name,
+        ))
+    }
+
+    /// Attaches the database file at `path` under the name `alias`, so tables
+    /// can be loaded from it with [`Database::load_attached`]. The path
+    /// `":memory:"` attaches a new in-memory database.
+    pub fn attach(&self, path: impl AsRef<Path>, alias: &str) -> Result<(), Error> {
+        execute(
+            &self.connection,
+            "ATTACH DATABASE ?1 AS ?2",
+            (path.as_ref().to_string_lossy(), alias),
+        )?;
+        Ok(())
+    }
+
+    /// Like [`Database::load`], but the table of `T` is created in and read
+    /// from the database attached as `alias`, see [`Database::attach`]. A
+    /// table of the same name in the main database stays separate. The alias
+    /// is kept as [`SqlTable::schema`] of the returned table. The table is not
+    /// registered, so [`Database::validate_schema`] does not check it.
+    pub fn load_attached<'a, T: ToTable<'a>>(&'a self, alias: &'a str) -> Result<T::Table, Error> {
+        let table = TableName {
+            schema: Some(alias),
+            name: T::NAME,
+        };
+        self.create::<T>(table)?;
+
+        Ok(T::Table::from_connection_with_schema(
+            &self.connection,
+            table.schema,
+            table.name,
+        ))
+    }
+
+    /// Remembers the expected columns of the table without creating it, so
+    /// [`Database::validate_schema`] checks it. Loading a table registers it
+    /// as well.
+    pub fn register<'a, T: ToTable<'a>>(&'a self) {
+        let mut registered_tables = self.registered_tables.borrow_mut();
+        if registered_tables.iter().any(|t| t.name == T::NAME) {
+            return;
+        }
+        registered_tables.push(schema::RegisteredTable {
+            name: T::NAME,
+            columns: table_columns::<T>(),
+        });
+    }
+
+    /// Runs arbitrary sql and reads every resulting row as `T`. The names of
+    /// the selected columns have to match the columns of `T`, so selecting
+    /// `*` from the table of `T` always works.
+    pub fn query_as<T: FromRow>(
+        &self,
+        sql: &str,
+        params: &[&dyn rusqlite::ToSql],
+    ) -> Result<Vec<T>, Error> {
+        debug_sql(sql);
+        let mut statement = self.connection.prepare(sql)?;
+        let mut rows = statement.query(params)?;
+        let mut result = Vec::new();
+        while let Some(row) = rows.next()? {
+            result.push(T::try_from_row(row, &self.connection)?);
+        }
+        Ok(result)
+    }
+
+    /// Drops the table of `T` including all its rows and indices and forgets
+    /// that it was registered. Does nothing, if the table does not exist.
+    /// Only the table of the main database is dropped, tables of the same
+    /// name in attached databases are kept.
+    pub fn drop_table<'a, T: ToTable<'a>>(&'a self) -> Result<(), Error> {
+        let table = TableName {
+            schema: Some("main"),
+            name: T::NAME,
+        };
+        execute(
+            &self.connection,
+            &format!("DROP TABLE IF EXISTS {table}"),
+            (),
+        )?;
+        self.registered_tables
+            .borrow_mut()
+            .retain(|t| t.name != T::NAME);
+        Ok(())
+    }
+
+    /// Checks that every registered table exists with exactly the expected
+    /// columns. Nothing is created or migrated. An empty list means the
+    /// schema is as expected.
+    pub fn validate_schema(&self) -> Result<Vec<schema::SchemaMismatch>, Error> {
+        let mut result = Vec::new();
+        for table in self.registered_tables.borrow().iter() {
+            result.extend(self.mismatches_of(table)?);
+        }
+        Ok(result)
+    }
+
+    /// Returns the differences between the table of `T` in the database and
+    /// the columns of `T`, without registering, creating or migrating the
+    /// table. An empty list means the table is as expected.
+    pub fn pending_alterations<'a, T: ToTable<'a>>(
+        &'a self,
+    ) -> Result<Vec<schema::SchemaMismatch>, Error> {
+        self.mismatches_of(&schema::RegisteredTable {
+            name: T::NAME,
+            columns: table_columns::<T>(),
+        })
+    }
+
+    /// Checks whether the table of `T` exists with exactly the columns of
+    /// `T`. Nothing is created or migrated.
+    pub fn schema_matches<'a, T: ToTable<'a>>(&'a self) -> Result<bool, Error> {
+        Ok(self.pending_alterations::<T>()?.is_empty())
+    }
+
+    fn mismatches_of(
+        &self,
+        table: &schema::RegisteredTable,
+    ) -> Result<Vec<schema::SchemaMismatch>, Error> {
+        if !self.connection.table_exists(Some("main"), table.name)? {
+            return Ok(vec![schema::SchemaMismatch::MissingTable {
+                table: table.name,
+            }]);
+        }
+        let main = TableName {
+            schema: Some("main"),
+            name: table.name,
+        };
+        let actual = schema::actual_columns(&self.connection, main)?;
+        Ok(schema::compare_columns(table, &actual))
+    }
+
+    /// Runs every migration, which has not been run on this database yet, in
+    /// ascending order of their version. The versions already run are stored
+    /// in the table [`migration::MIGRATIONS_TABLE`]. If a migration fails, its
+    /// changes are rolled back and the later ones are not run. Returns how
+    /// many migrations were run.
+    pub fn run_migrations(&self, migrations: &[migration::Migration]) -> Result<usize, Error> {
+        migration::run(&self.connection, migrations)
+    }
+
+    /// Reads the columns of an existing table from the database, without
+    /// needing a type for it. Returns an empty list for a missing table. A
+    /// plain name is looked up like sqlite does, in the main database first
+    /// and then in the attached ones. Qualify it to read a table of one
+    /// attached database.
+    pub fn introspect<'n>(&self, table: impl Into<TableName<'n>>) -> Result<Vec<SqlColumn>, Error> {
+        let table = table.into();
+        Ok(schema::introspect(&self.connection, table)?)
+    }
+
+    /// Rebuilds the database file, so the space of deleted rows is given back
+    /// to the file system. Fails inside of a transaction.
+    pub fn vacuum(&self) -> Result<(), Error> {
+        debug_sql("VACUUM");
+        self.connection.execute_batch("VACUUM")?;
+        Ok(())
+    }
+
+    /// Copies the write-ahead log of a database in WAL mode into the database
+    /// file. Returns false if the checkpoint could not complete, because
+    /// another connection was busy. Does nothing outside of WAL mode.
+    pub fn checkpoint(&self, mode: CheckpointMode) -> Result<bool, Error> {
+        let sql = format!("PRAGMA wal_checkpoint({})", mode.as_sql());
+        debug_sql(&sql);
+        let is_busy: bool = self.connection.query_row(&sql, (), |r| r.get(0))?;
+        Ok(!is_busy)
+    }
+
+    /// Lets SQLite update the statistics it uses to plan queries. Cheap
+    /// enough to run before closing a long-lived connection.
+    pub fn optimize(&self) -> Result<(), Error> {
+        debug_sql("PRAGMA optimize");
+        self.connection.execute_batch("PRAGMA optimize")?;
+        Ok(())
+    }
+
+    /// Creates the table in its attached database, or the main database if
+    /// it has no schema.
+    fn create<'a, T: ToTable<'a>>(&'a self, table: TableName<'_>) -> Result<(), Error> {
+        // Without a schema, sqlite would find the table in any attached
+        // database as well.
+        let schema_name = table.schema.unwrap_or("main");
+        if self.connection.is_readonly(schema_name)? {
+            if !self
+                .connection
+                .table_exists(Some(schema_name), table.name)?
+            {
+                return Err(Error::MissingTable(table.name.to_string().into()));
+            }
             return Ok(());
         }
-        let mut sql = "CREATE TABLE IF NOT EXISTS \"".to_string();
+        if !self
+            .connection
+            .table_exists(Some(schema_name), table.name)?
+        {
+            self.create_table::<T>(table)?;
+        } else {
+            self.add_default_columns::<T>(table)?;
+        }
+        // Indices are created for existing tables too, so adding
+        // `#[silo(index)]` to a field takes effect on the next load.
+        self.create_indices::<T>(table)
+    }
 
-        sql.push_str(T::NAME);
-        sql.push_str("\" (");
-        for (i, column) in T::columns(None, false, false).into_iter().enumerate() {
+    fn create_table<'a, T: ToTable<'a>>(&'a self, table: TableName<'_>) -> Result<(), Error> {
+        let mut sql = format!("CREATE TABLE IF NOT EXISTS {table} (");
+        for (i, column) in table_columns::<T>().iter().enumerate() {
             if i > 0 {
                 sql.push(',');
             }
-            sql.push('"');
-            sql.push_str(&column.name);
-            sql.push('"');
-            sql.push(' ');
-            sql.push_str(column.r#type.as_sql());
-            if column.is_unique {
-                sql.push_str(" UNIQUE");
-            }
-            if column.is_primary {
-                sql.push_str(" PRIMARY KEY");
-            }
+            write_column_definition(&mut sql, column, T::IS_STRICT, false);
+        }
+        for columns in T::UNIQUE_CONSTRAINTS {
+            let columns = columns
+                .iter()
+                .map(|c| format!("\"{c}\""))
+                .collect::<Vec<_>>()
+                .join(", ");
+            _ = write!(sql, ", UNIQUE({columns})");
         }
-        // TODO: Add strict mode here: https://sqlite.org/stricttables.html
-        sql.push_str(");");
+        sql.push(')');
+        if T::IS_STRICT {
+            sql.push_str(" STRICT");
+        }
+        sql.push(';');
         debug_sql(&sql);
 
         self.connection.execute(&sql, ())?;
         Ok(())
     }
+
+    /// Adds the columns with a default, which are missing from an existing
+    /// table. Other missing columns are left to `validate_schema`.
+    fn add_default_columns<'a, T: ToTable<'a>>(
+        &'a self,
+        table: TableName<'_>,
+    ) -> Result<(), Error> {
+        let actual = schema::actual_columns(&self.connection, table)?;
+        for column in table_columns::<T>() {
+            if column.default.is_none() || actual.iter().any(|a| a.name == column.name) {
+                continue;
+            }
+            let mut sql = format!("ALTER TABLE {table} ADD COLUMN ");
+            write_column_definition(&mut sql, &column, T::IS_STRICT, true);
+            execute(&self.connection, &sql, ())?;
+        }
+        Ok(())
+    }
+
+    /// The indices are created in the database of the table as well, the
+    /// table they are on cannot be qualified.
+    fn create_indices<'a, T: ToTable<'a>>(&'a self, table: TableName<'_>) -> Result<(), Error> {
+        let on = Quoted(table.name);
+        for column in T::columns(None, false, false)
+            .into_iter()
+            .filter(|c| c.is_indexed)
+        {
+            let sql = format!(
+                "CREATE INDEX IF NOT EXISTS {index} ON {on}(\"{column}\")",
+                index = table.sibling(&format!("idx_{}_{}", table.name, column.name)),
+                column = column.name
+            );
+            execute(&self.connection, &sql, ())?;
+        }
+        for column in T::columns(None, false, false)
+            .into_iter()
+            .filter(|c| c.is_unique_indexed)
+        {
+            let sql = format!(
+                "CREATE UNIQUE INDEX IF NOT EXISTS {index} ON {on}(\"{column}\") WHERE \"{column}\" IS NOT NULL",
+                index = table.sibling(&format!("uidx_{}_{}", table.name, column.name)),
+                column = column.name
+            );
+            execute(&self.connection, &sql, ())?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes the name, type and constraints of the column, like CREATE TABLE
+/// expects them. A column added to an existing table cannot be UNIQUE, a
+/// PRIMARY KEY or reference another table, so `is_added` leaves these out.
+fn write_column_definition(sql: &mut String, column: &SqlColumn, is_strict: bool, is_added: bool) {
+    _ = write!(
+        sql,
+        "\"{}\" {}",
+        column.name,
+        column.r#type.as_strict_sql(is_strict)
+    );
+    if column.is_nocase {
+        sql.push_str(" COLLATE NOCASE");
+    }
+    if column.is_unique && !is_added {
+        sql.push_str(" UNIQUE");
+    }
+    if column.is_primary && !is_added {
+        sql.push_str(" PRIMARY KEY");
+        if column.is_autoincrement {
+            sql.push_str(" AUTOINCREMENT");
+        }
+    }
+    if column.r#type.is_not_null() {
+        sql.push_str(" NOT NULL");
+    }
+    if let Some(default) = &column.default {
+        _ = write!(sql, " DEFAULT {default}");
+    }
+    if let Some(table) = column.references.as_ref().filter(|_| !is_added) {
+        _ = write!(sql, " REFERENCES {}", Quoted(table));
+    }
+    if let Some(check) = &column.check {
+        _ = write!(sql, " CHECK ({check})");
+    }
 }
 
 /// This trait represents the columns, that may be part of a struct. Each Column
@@ -333,6 +928,12 @@ impl<T: AsColumns> AsColumns for Option<T> {
 impl<T: AsColumnsDynamicallySized> AsColumnsDynamicallySized for Option<T> {
     fn columns(parent: Option<&str>, is_unique: bool, is_primary: bool) -> Vec<SqlColumn> {
         T::columns(parent, is_unique, is_primary)
+            .into_iter()
+            .map(|c| SqlColumn {
+                r#type: SqlColumnType::to_optional(c.r#type),
+                ..c
+            })
+            .collect()
     }
 }
 
@@ -340,6 +941,9 @@ impl<T: AsColumnsDynamicallySized> AsColumnsDynamicallySized for Option<T> {
 /// AsColumns for free.
 pub trait IsSingleColumn {
     const SQL_COLUMN_TYPE: SqlColumnType;
+    /// A condition, which every value has to satisfy. It follows the name of
+    /// the column in a CHECK constraint, e.g. `IN (0, 1)`.
+    const CHECK: Option<&'static str> = None;
 }
 
 impl<T: IsSingleColumn> AsColumns for T {
@@ -348,11 +952,21 @@ impl<T: IsSingleColumn> AsColumns for T {
 
 impl<T: IsSingleColumn> AsColumnsDynamicallySized for T {
     fn columns(parent: Option<&str>, is_unique: bool, is_primary: bool) -> Vec<SqlColumn> {
+        let name = parent.unwrap();
         vec![SqlColumn {
-            name: parent.unwrap().to_string().into(),
+            name: name.to_string().into(),
             r#type: T::SQL_COLUMN_TYPE,
             is_primary,
             is_unique,
+            is_indexed: false,
+            is_unique_indexed: false,
+            is_nocase: false,
+            default: None,
+            references: None,
+            is_skip_insert: false,
+            is_autoincrement: false,
+            is_skip_select: false,
+            check: T::CHECK.map(|check| format!("\"{name}\" {check}").into()),
         }]
     }
 }
@@ -377,7 +991,7 @@ macro_rules! impl_as_params {
                     Err(rusqlite::Error::InvalidColumnType(.., t)) => {
                         Err(Error::WrongColumnType(stringify!($t).into(), t))
                     }
-                    Err(err) => unreachable!("Impossible error? {err}"),
+                    Err(err) => Err(err.into()),
                 }
             }
         }
@@ -396,7 +1010,39 @@ macro_rules! impl_as_params_base {
     };
 }
 
-impl_as_params!(bool, SqlColumnType::Integer);
+impl partial::HasPartial for bool {
+    type Partial = Option<bool>;
+}
+
+impl IsSingleColumn for bool {
+    const SQL_COLUMN_TYPE: SqlColumnType = SqlColumnType::Integer;
+    const CHECK: Option<&'static str> = Some("IN (0, 1)");
+}
+
+impl AsParams for bool {
+    fn as_params<'b>(&'b self) -> Vec<ToSqlDyn<'b>> {
+        vec![ToSqlDyn::Borrowed(self)]
+    }
+}
+
+/// Unlike rusqlite, which reads every integer other than 0 as true, only 0
+/// and 1 are read, other values are ill formatted.
+impl ExtractFromRow for bool {
+    fn try_from_row_simple(column_name: &str, row: &rusqlite::Row) -> Result<Self, Error> {
+        match i64::try_from_row_simple(column_name, row) {
+            Ok(0) => Ok(false),
+            Ok(1) => Ok(true),
+            Ok(it) => Err(Error::IllFormattedColumn(
+                "bool".into(),
+                it.to_string(),
+                None,
+            )),
+            Err(Error::WrongColumnType(_, t)) => Err(Error::WrongColumnType("bool".into(), t)),
+            Err(err) => Err(err),
+        }
+    }
+}
+
 impl_as_params!(i8, SqlColumnType::Integer);
 impl_as_params!(i16, SqlColumnType::Integer);
 impl_as_params!(i32, SqlColumnType::Integer);
@@ -430,28 +1076,44 @@ impl ExtractFromRow for u64 {
             Err(rusqlite::Error::InvalidColumnType(.., t)) => {
                 Err(Error::WrongColumnType(stringify!(u64).into(), t))
             }
-            Err(err) => unreachable!("Impossible error? {err}"),
+            Err(err) => Err(err.into()),
         }
     }
 }
 
-impl_as_params!(Time, SqlColumnType::Text);
-impl_as_params!(Date, SqlColumnType::Text);
-impl_as_params!(DateTime<Utc>, SqlColumnType::Text);
+#[cfg(feature = "time")]
+impl_as_params!(time::Time, SqlColumnType::Text);
+#[cfg(feature = "time")]
+impl_as_params!(time::Date, SqlColumnType::Text);
+#[cfg(feature = "time")]
+impl_as_params!(time::OffsetDateTime, SqlColumnType::Text);
+#[cfg(feature = "chrono")]
+impl_as_params!(chrono::DateTime<chrono::Utc>, SqlColumnType::Text);
+#[cfg(feature = "chrono")]
+impl_as_params!(chrono::NaiveDate, SqlColumnType::Text);
+#[cfg(feature = "chrono")]
+impl_as_params!(chrono::NaiveTime, SqlColumnType::Text);
+#[cfg(feature = "chrono")]
+impl_as_params!(chrono::NaiveDateTime, SqlColumnType::Text);
+#[cfg(feature = "uuid")]
 impl_as_params_base!(NonNilUuid, SqlColumnType::Text);
+#[cfg(feature = "uuid")]
 impl_as_params_base!(Uuid, SqlColumnType::Text);
+#[cfg(feature = "uuid")]
 impl AsParams for Uuid {
     fn as_params<'b>(&'b self) -> Vec<ToSqlDyn<'b>> {
         vec![ToSqlDyn::Boxed(Box::new(self.to_string()))]
     }
 }
 
+#[cfg(feature = "uuid")]
 impl AsParams for NonNilUuid {
     fn as_params<'b>(&'b self) -> Vec<ToSqlDyn<'b>> {
         vec![ToSqlDyn::Boxed(Box::new(self.get().to_string()))]
     }
 }
 
+#[cfg(feature = "uuid")]
 impl ExtractFromRow for Uuid {
     fn try_from_row_simple(column_name: &str, row: &rusqlite::Row) -> Result<Self, Error> {
         match row.get::<&str, String>(column_name) {
@@ -463,11 +1125,12 @@ impl ExtractFromRow for Uuid {
             Err(rusqlite::Error::InvalidColumnType(.., t)) => {
                 Err(Error::WrongColumnType("Uuid".into(), t))
             }
-            Err(err) => unreachable!("Impossible error? {err}"),
+            Err(err) => Err(err.into()),
         }
     }
 }
 
+#[cfg(feature = "uuid")]
 impl ExtractFromRow for NonNilUuid {
     fn try_from_row_simple(column_name: &str, row: &rusqlite::Row) -> Result<Self, Error> {
         match row.get::<&str, String>(column_name) {
@@ -481,150 +1144,769 @@ impl ExtractFromRow for NonNilUuid {
             Err(rusqlite::Error::InvalidColumnType(.., t)) => {
                 Err(Error::WrongColumnType("Uuid".into(), t))
             }
-            Err(err) => unreachable!("Impossible error? {err}"),
+            Err(err) => Err(err.into()),
         }
     }
 }
 
-impl_as_params!(OffsetDateTime, SqlColumnType::Text);
 impl_as_params!(f32, SqlColumnType::Float);
 impl_as_params!(f64, SqlColumnType::Float);
 impl_as_params!(String, SqlColumnType::Text);
+impl_as_params_base!(Cow<'static, str>, SqlColumnType::Text);
 
-pub trait FromRow: Sized {
-    fn try_from_row(row: &rusqlite::Row, connection: &rusqlite::Connection) -> Result<Self, Error>;
+impl AsParams for Cow<'static, str> {
+    fn as_params<'b>(&'b self) -> Vec<ToSqlDyn<'b>> {
+        vec![ToSqlDyn::Borrowed(self)]
+    }
 }
 
-pub trait ExtractFromRow: Sized {
-    fn try_from_row_simple(column_name: &str, row: &rusqlite::Row) -> Result<Self, Error>;
-    fn try_from_row(
-        column_name: &str,
-        row: &rusqlite::Row,
-        _connection: &rusqlite::Connection,
-    ) -> Result<Self, Error> {
-        Self::try_from_row_simple(column_name, row)
+impl ExtractFromRow for Cow<'static, str> {
+    fn try_from_row_simple(column_name: &str, row: &rusqlite::Row) -> Result<Self, Error> {
+        String::try_from_row_simple(column_name, row).map(Cow::Owned)
     }
 }
+impl_as_params_base!(char, SqlColumnType::Text);
 
-// TODO: Is this right? Kind of depends on the reason of failure, doesn't it?
-impl<T: ExtractFromRow> ExtractFromRow for Option<T> {
+impl AsParams for char {
+    fn as_params<'b>(&'b self) -> Vec<ToSqlDyn<'b>> {
+        vec![ToSqlDyn::Boxed(Box::new(self.to_string()))]
+    }
+}
+
+impl ExtractFromRow for char {
     fn try_from_row_simple(column_name: &str, row: &rusqlite::Row) -> Result<Self, Error> {
-        match T::try_from_row_simple(column_name, row) {
-            Ok(it) => Ok(Some(it)),
-            Err(_) => Ok(None),
+        match row.get::<&str, String>(column_name) {
+            Ok(it) => {
+                let mut chars = it.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Ok(c),
+                    _ => Err(Error::IllFormattedColumn("char".into(), it, None)),
+                }
+            }
+            Err(rusqlite::Error::InvalidColumnName(_)) => {
+                Err(Error::MissingColumn(column_name.to_string().into()))
+            }
+            Err(rusqlite::Error::InvalidColumnType(.., t)) => {
+                Err(Error::WrongColumnType("char".into(), t))
+            }
+            Err(err) => Err(err.into()),
         }
     }
 }
 
-impl<T: FromRow> FromRow for Option<T> {
-    fn try_from_row(row: &rusqlite::Row, connection: &rusqlite::Connection) -> Result<Self, Error> {
-        match T::try_from_row(row, connection) {
-            Ok(it) => Ok(Some(it)),
-            Err(_) => Ok(None),
-        }
+/// Durations are stored as whole nanoseconds. Durations longer than about 292
+/// years do not fit and are stored as `i64::MAX` nanoseconds.
+pub(crate) fn duration_as_nanos(duration: &std::time::Duration) -> i64 {
+    i64::try_from(duration.as_nanos()).unwrap_or(i64::MAX)
+}
+
+impl_as_params_base!(std::time::Duration, SqlColumnType::Integer);
+
+impl AsParams for std::time::Duration {
+    fn as_params<'b>(&'b self) -> Vec<ToSqlDyn<'b>> {
+        vec![ToSqlDyn::Boxed(Box::new(duration_as_nanos(self)))]
     }
 }
 
-pub trait ToTable<'a>: AsParams + AsColumns + FromRow {
-    const NAME: &'static str;
-    type Table: SqlTable<'a>;
+impl ExtractFromRow for std::time::Duration {
+    fn try_from_row_simple(column_name: &str, row: &rusqlite::Row) -> Result<Self, Error> {
+        match row.get::<&str, i64>(column_name) {
+            Ok(it) => u64::try_from(it)
+                .map(std::time::Duration::from_nanos)
+                .map_err(|e| {
+                    Error::IllFormattedColumn("Duration".into(), it.to_string(), Some(Box::new(e)))
+                }),
+            Err(rusqlite::Error::InvalidColumnName(_)) => {
+                Err(Error::MissingColumn(column_name.to_string().into()))
+            }
+            Err(rusqlite::Error::InvalidColumnType(.., t)) => {
+                Err(Error::WrongColumnType("Duration".into(), t))
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
 }
 
-impl<'a, T: ToTable<'a>> ToTable<'a> for Option<T> {
-    const NAME: &'static str = T::NAME;
+/// IP addresses are stored as text in their canonical form, e.g. `127.0.0.1`
+/// or `::1`.
+macro_rules! impl_as_params_ip_addr {
+    ($t:ty, $name:literal) => {
+        impl_as_params_base!($t, SqlColumnType::Text);
 
-    type Table = T::Table;
+        impl AsParams for $t {
+            fn as_params<'b>(&'b self) -> Vec<ToSqlDyn<'b>> {
+                vec![ToSqlDyn::Boxed(Box::new(self.to_string()))]
+            }
+        }
+
+        impl ExtractFromRow for $t {
+            fn try_from_row_simple(column_name: &str, row: &rusqlite::Row) -> Result<Self, Error> {
+                match row.get::<&str, String>(column_name) {
+                    Ok(it) => it.parse().map_err(|e| {
+                        Error::IllFormattedColumn($name.into(), it, Some(Box::new(e)))
+                    }),
+                    Err(rusqlite::Error::InvalidColumnName(_)) => {
+                        Err(Error::MissingColumn(column_name.to_string().into()))
+                    }
+                    Err(rusqlite::Error::InvalidColumnType(.., t)) => {
+                        Err(Error::WrongColumnType($name.into(), t))
+                    }
+                    Err(err) => Err(err.into()),
+                }
+            }
+        }
+    };
 }
 
-// #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
-// pub enum SqlFailureBehavior {
-//     #[default]
-//     Abort,
-//     Fail,
-//     Ignore,
-//     Replace,
-//     Rollback,
-// }
-
-// impl Display for SqlFailureBehavior {
-//     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-//         match self {
-//             SqlFailureBehavior::Abort => write!(f, "ABORT"),
-//             SqlFailureBehavior::Fail => write!(f, "FAIL"),
-//             SqlFailureBehavior::Ignore => write!(f, "IGNORE"),
-//             SqlFailureBehavior::Replace => write!(f, "REPLACE"),
-//             SqlFailureBehavior::Rollback => write!(f, "ROLLBACK"),
-//         }
-//     }
-// }
+impl_as_params_ip_addr!(std::net::IpAddr, "IpAddr");
+impl_as_params_ip_addr!(std::net::Ipv4Addr, "Ipv4Addr");
+impl_as_params_ip_addr!(std::net::Ipv6Addr, "Ipv6Addr");
 
-pub trait SqlTable<'a>: Sized {
-    type RowType: ToTable<'a>;
-    type ValueType: partial::HasPartial;
-    type FilterType: filter::Filter;
-    // const INSERT_FAILURE_BEHAVIOR: SqlFailureBehavior;
-    fn from_connection(connection: &'a Connection) -> Self;
-    fn connection(&self) -> &'a Connection;
+/// Paths and OS strings are stored as text. Parts, which are not valid
+/// UTF-8, are replaced with `U+FFFD`, so such values do not round-trip.
+macro_rules! impl_as_params_os_str {
+    ($t:ty, $name:literal) => {
+        impl_as_params_base!($t, SqlColumnType::Text);
 
-    fn insert(&self, row: Self::RowType) -> Result<bool, rusqlite::Error>;
-    fn load_where(
-        &self,
-        filter: impl Into<Self::FilterType>,
-    ) -> Result<Vec<Self::RowType>, rusqlite::Error>;
-    fn update(
-        &self,
-        filter: impl Into<Self::FilterType>,
-        updated: <Self::ValueType as partial::HasPartial>::Partial,
-    ) -> Result<usize, rusqlite::Error>;
-    fn project<P: Projectable>(
-        &self,
-        columns: impl Into<ProjectionColumns>,
+        impl AsParams for $t {
+            fn as_params<'b>(&'b self) -> Vec<ToSqlDyn<'b>> {
+                vec![ToSqlDyn::Boxed(Box::new(
+                    std::ffi::OsStr::new(self).to_string_lossy().into_owned(),
+                ))]
+            }
+        }
+
+        impl ExtractFromRow for $t {
+            fn try_from_row_simple(column_name: &str, row: &rusqlite::Row) -> Result<Self, Error> {
+                match row.get::<&str, String>(column_name) {
+                    Ok(it) => Ok(it.into()),
+                    Err(rusqlite::Error::InvalidColumnName(_)) => {
+                        Err(Error::MissingColumn(column_name.to_string().into()))
+                    }
+                    Err(rusqlite::Error::InvalidColumnType(.., t)) => {
+                        Err(Error::WrongColumnType($name.into(), t))
+                    }
+                    Err(err) => Err(err.into()),
+                }
+            }
+        }
+    };
+}
+
+impl_as_params_os_str!(std::path::PathBuf, "PathBuf");
+impl_as_params_os_str!(std::ffi::OsString, "OsString");
+
+// Bytes are stored as a single blob, not as one row per element.
+impl_as_params!(Vec<u8>, SqlColumnType::Blob);
+
+/// 128 bit integers do not fit into a sqlite integer, so they are stored as a
+/// blob of 16 big endian bytes. The sign bit of signed integers is flipped, so
+/// that comparing two blobs compares the integers. Range filters therefore
+/// have to compare against [`Int128::to_blob`] of the bound.
+pub trait Int128: Sized {
+    fn to_blob(self) -> [u8; 16];
+    fn from_blob(blob: [u8; 16]) -> Self;
+}
+
+macro_rules! impl_int128 {
+    ($t:ty, $flip:expr) => {
+        impl Int128 for $t {
+            fn to_blob(self) -> [u8; 16] {
+                (self ^ $flip).to_be_bytes()
+            }
+
+            fn from_blob(blob: [u8; 16]) -> Self {
+                <$t>::from_be_bytes(blob) ^ $flip
+            }
+        }
+
+        impl_as_params_base!($t, SqlColumnType::Blob);
+
+        impl AsParams for $t {
+            fn as_params<'b>(&'b self) -> Vec<ToSqlDyn<'b>> {
+                vec![ToSqlDyn::Boxed(Box::new(self.to_blob().to_vec()))]
+            }
+        }
+
+        impl ExtractFromRow for $t {
+            fn try_from_row_simple(column_name: &str, row: &rusqlite::Row) -> Result<Self, Error> {
+                match row.get::<&str, Vec<u8>>(column_name) {
+                    Ok(it) => match <[u8; 16]>::try_from(it.as_slice()) {
+                        Ok(blob) => Ok(<$t>::from_blob(blob)),
+                        Err(e) => Err(Error::IllFormattedColumn(
+                            stringify!($t).into(),
+                            format!("{it:?}"),
+                            Some(Box::new(e)),
+                        )),
+                    },
+                    Err(rusqlite::Error::InvalidColumnName(_)) => {
+                        Err(Error::MissingColumn(column_name.to_string().into()))
+                    }
+                    Err(rusqlite::Error::InvalidColumnType(.., t)) => {
+                        Err(Error::WrongColumnType(stringify!($t).into(), t))
+                    }
+                    Err(err) => Err(err.into()),
+                }
+            }
+        }
+    };
+}
+
+impl_int128!(u128, 0);
+impl_int128!(i128, i128::MIN);
+
+pub trait FromRow: Sized {
+    fn try_from_row(row: &rusqlite::Row, connection: &rusqlite::Connection) -> Result<Self, Error>;
+}
+
+pub trait ExtractFromRow: Sized {
+    fn try_from_row_simple(column_name: &str, row: &rusqlite::Row) -> Result<Self, Error>;
+    fn try_from_row(
+        column_name: &str,
+        row: &rusqlite::Row,
+        _connection: &rusqlite::Connection,
+    ) -> Result<Self, Error> {
+        Self::try_from_row_simple(column_name, row)
+    }
+}
+
+// TODO: Is this right? Kind of depends on the reason of failure, doesn't it?
+impl<T: ExtractFromRow> ExtractFromRow for Option<T> {
+    fn try_from_row_simple(column_name: &str, row: &rusqlite::Row) -> Result<Self, Error> {
+        match T::try_from_row_simple(column_name, row) {
+            Ok(it) => Ok(Some(it)),
+            Err(_) => Ok(None),
+        }
+    }
+}
+
+impl<T: FromRow> FromRow for Option<T> {
+    fn try_from_row(row: &rusqlite::Row, connection: &rusqlite::Connection) -> Result<Self, Error> {
+        match T::try_from_row(row, connection) {
+            Ok(it) => Ok(Some(it)),
+            Err(_) => Ok(None),
+        }
+    }
+}
+
+/// A Result is stored like a two variant enum. The column named after the
+/// field holds the variant ("Ok" or "Err"), followed by the columns of the
+/// payloads, prefixed with `_ok` and `_err`. The columns of the variant, which
+/// is not used, are set to NULL.
+impl<T: AsColumns, E: AsColumns> AsColumns for Result<T, E> {
+    const COLUMN_COUNT: usize = 1 + T::COLUMN_COUNT + E::COLUMN_COUNT;
+}
+
+impl<T: AsColumns, E: AsColumns> AsColumnsDynamicallySized for Result<T, E> {
+    fn columns(parent: Option<&str>, is_unique: bool, is_primary: bool) -> Vec<SqlColumn> {
+        let parent = parent.expect("Results need a column name.");
+        let mut result =
+            <String as AsColumnsDynamicallySized>::columns(Some(parent), is_unique, is_primary);
+        result.extend(
+            T::columns(Some(&format!("{parent}_ok")), false, false)
+                .into_iter()
+                .chain(E::columns(Some(&format!("{parent}_err")), false, false))
+                .map(|c| SqlColumn {
+                    r#type: SqlColumnType::to_optional(c.r#type),
+                    ..c
+                }),
+        );
+        result
+    }
+}
+
+impl<T: AsParams + AsColumns, E: AsParams + AsColumns> AsParams for Result<T, E> {
+    fn as_params<'b>(&'b self) -> Vec<ToSqlDyn<'b>> {
+        let mut result = Vec::with_capacity(Self::COLUMN_COUNT);
+        match self {
+            Ok(it) => {
+                result.push(ToSqlDyn::create_static(&"Ok"));
+                result.extend(it.as_params());
+                result.extend((0..E::COLUMN_COUNT).map(|_| ToSqlDyn::create_static(&Null)));
+            }
+            Err(err) => {
+                result.push(ToSqlDyn::create_static(&"Err"));
+                result.extend((0..T::COLUMN_COUNT).map(|_| ToSqlDyn::create_static(&Null)));
+                result.extend(err.as_params());
+            }
+        }
+        result
+    }
+}
+
+impl<T: ExtractFromRow, E: ExtractFromRow> ExtractFromRow for Result<T, E> {
+    fn try_from_row_simple(column_name: &str, row: &rusqlite::Row) -> Result<Self, Error> {
+        let variant = String::try_from_row_simple(column_name, row)?;
+        match variant.as_str() {
+            "Ok" => Ok(Ok(T::try_from_row_simple(
+                &format!("{column_name}_ok"),
+                row,
+            )?)),
+            "Err" => Ok(Err(E::try_from_row_simple(
+                &format!("{column_name}_err"),
+                row,
+            )?)),
+            _ => Err(Error::IllFormattedColumn("Result".into(), variant, None)),
+        }
+    }
+}
+
+impl<T: AsColumns, E: AsColumns> partial::HasPartial for Result<T, E> {
+    type Partial = Option<Result<T, E>>;
+}
+
+pub trait ToTable<'a>: AsParams + AsColumns + FromRow {
+    const NAME: &'static str;
+    /// Set by `#[silo(strict)]`, the table is created as a STRICT table,
+    /// which rejects values of the wrong type.
+    const IS_STRICT: bool = false;
+    /// Set by `#[silo(unique(..))]`, every entry lists the columns, which are
+    /// unique together.
+    const UNIQUE_CONSTRAINTS: &'static [&'static [&'static str]] = &[];
+    /// Set by `#[silo(soft_delete)]`, the table has an additional column
+    /// [`SOFT_DELETE_COLUMN`] and deleting rows only sets it.
+    const IS_SOFT_DELETE: bool = false;
+    /// Set by `#[silo(replace(..))]`, replacing a row only resolves conflicts
+    /// on these columns, by updating the existing row. Empty if any conflict
+    /// replaces the conflicting rows.
+    const REPLACE_CONFLICT_COLUMNS: &'static [&'static str] = &[];
+    type Table: SqlTable<'a>;
+}
+
+impl<'a, T: ToTable<'a>> ToTable<'a> for Option<T> {
+    const NAME: &'static str = T::NAME;
+    const IS_STRICT: bool = T::IS_STRICT;
+    const UNIQUE_CONSTRAINTS: &'static [&'static [&'static str]] = T::UNIQUE_CONSTRAINTS;
+    const IS_SOFT_DELETE: bool = T::IS_SOFT_DELETE;
+    const REPLACE_CONFLICT_COLUMNS: &'static [&'static str] = T::REPLACE_CONFLICT_COLUMNS;
+
+    type Table = T::Table;
+}
+
+/// The column of a `#[silo(soft_delete)]` table, which holds the time a row
+/// was deleted, or NULL if it was not. It is not part of the row type, but
+/// can be read with a projection. The time is stored as text in UTC, like
+/// `2025-01-31 12:00:00.000+00:00`, so it reads as an `Option<String>`, or as
+/// an `Option<OffsetDateTime>` with the `time` feature.
+pub const SOFT_DELETE_COLUMN: &str = "deleted_at";
+
+/// The columns of the table of `T`, including the ones, which are not part of
+/// `T` itself.
+fn table_columns<'a, T: ToTable<'a>>() -> Vec<SqlColumn> {
+    let mut columns = T::columns(None, false, false);
+    if T::IS_SOFT_DELETE {
+        columns.extend(
+            <Option<String> as AsColumnsDynamicallySized>::columns(
+                Some(SOFT_DELETE_COLUMN),
+                false,
+                false,
+            )
+            .into_iter()
+            // A default lets load add the column to existing tables.
+            .map(|c| SqlColumn {
+                default: Some("NULL".into()),
+                ..c
+            }),
+        );
+    }
+    columns
+}
+
+/// Implemented by the derive for every table with a `#[silo(primary)]` field.
+pub trait HasPrimaryKey {
+    type PrimaryKey;
+    fn primary_key(&self) -> &Self::PrimaryKey;
+}
+
+/// What sqlite does, if an insert violates a constraint. See
+/// <https://sqlite.org/lang_conflict.html>.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SqlFailureBehavior {
+    #[default]
+    Abort,
+    Fail,
+    Ignore,
+    Replace,
+    Rollback,
+}
+
+/// An aggregate function over a numeric column, see [`SqlTable::aggregate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregate {
+    Sum,
+    Min,
+    Max,
+    Avg,
+}
+
+impl Display for SqlFailureBehavior {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SqlFailureBehavior::Abort => write!(f, "ABORT"),
+            SqlFailureBehavior::Fail => write!(f, "FAIL"),
+            SqlFailureBehavior::Ignore => write!(f, "IGNORE"),
+            SqlFailureBehavior::Replace => write!(f, "REPLACE"),
+            SqlFailureBehavior::Rollback => write!(f, "ROLLBACK"),
+        }
+    }
+}
+
+pub trait SqlTable<'a>: Sized {
+    type RowType: ToTable<'a>;
+    type ValueType: partial::HasPartial;
+    type FilterType: filter::Filter;
+    /// Set by `#[silo(ignore)]`, `#[silo(replace)]` and so on, used by
+    /// [`Self::insert`].
+    const INSERT_FAILURE_BEHAVIOR: SqlFailureBehavior;
+    fn from_connection(connection: &'a Connection) -> Self {
+        Self::from_connection_with_name(connection, Self::RowType::NAME)
+    }
+    /// Uses the table `name` instead of the name of [`Self::RowType`], see
+    /// [`Database::load_as`].
+    fn from_connection_with_name(connection: &'a Connection, name: &'a str) -> Self {
+        Self::from_connection_with_schema(connection, None, name)
+    }
+    /// Uses the table `name` of the database attached as `schema`, see
+    /// [`Database::load_attached`]. None is the main database.
+    fn from_connection_with_schema(
+        connection: &'a Connection,
+        schema: Option<&'a str>,
+        name: &'a str,
+    ) -> Self;
+    fn connection(&self) -> &'a Connection;
+    /// The name of the table in the database, which is the name of
+    /// [`Self::RowType`] unless the table was loaded with a different one.
+    fn name(&self) -> &'a str;
+    /// The alias of the attached database the table is in, None for the main
+    /// database.
+    fn schema(&self) -> Option<&'a str>;
+    /// The name of the table qualified with [`Self::schema`], like the sql
+    /// builders take it.
+    fn table_name(&self) -> TableName<'a> {
+        TableName {
+            schema: self.schema(),
+            name: self.name(),
+        }
+    }
+
+    fn insert(&self, row: Self::RowType) -> Result<bool, Error>;
+    /// Like [`Self::insert`], but returns the rowid of the new row, which is
+    /// the value of an `#[silo(autoincrement)]` primary key. Returns None if
+    /// no row was inserted.
+    fn insert_returning_id(&self, row: Self::RowType) -> Result<Option<i64>, Error> {
+        insert_returning_id(
+            &self.connection(),
+            self.table_name(),
+            row,
+            Self::INSERT_FAILURE_BEHAVIOR,
+        )
+    }
+    /// Like [`Self::insert`], but returns the row as it was stored, with the
+    /// defaults and ids assigned by the database. Returns None if no row was
+    /// inserted.
+    fn insert_returning(&self, row: Self::RowType) -> Result<Option<Self::RowType>, Error> {
+        insert_returning(
+            &self.connection(),
+            self.table_name(),
+            row,
+            Self::INSERT_FAILURE_BEHAVIOR,
+        )
+    }
+    /// Inserts the row with the given failure behavior instead of
+    /// [`Self::INSERT_FAILURE_BEHAVIOR`]. Returns false if no row was
+    /// inserted.
+    fn insert_with(&self, row: Self::RowType, behavior: SqlFailureBehavior) -> Result<bool, Error> {
+        insert_into_table_with(&self.connection(), self.table_name(), row, behavior)
+    }
+    /// Checks, whether inserting the row would succeed, without inserting it.
+    /// Returns false if the row violates a constraint, like [`Self::insert`].
+    fn validate_insert(&self, row: &Self::RowType) -> Result<bool, Error> {
+        validate_insert(self.connection(), self.table_name(), row)
+    }
+    /// Inserts the row or updates the row, which conflicts with it on
+    /// `conflict_columns`. Pass an empty slice to use the primary key. Returns
+    /// `Error::MissingColumn` for an unknown conflict column and
+    /// `Error::MissingConflictColumns` for an empty slice on a table without
+    /// a primary key.
+    fn upsert(&self, row: Self::RowType, conflict_columns: &[&str]) -> Result<bool, Error> {
+        upsert(self.connection(), self.table_name(), row, conflict_columns)
+    }
+    fn load_where(&self, filter: impl Into<Self::FilterType>) -> Result<Vec<Self::RowType>, Error>;
+    fn update(
+        &self,
+        filter: impl Into<Self::FilterType>,
+        updated: <Self::ValueType as partial::HasPartial>::Partial,
+    ) -> Result<usize, Error>;
+    fn project<P: Projectable>(
+        &self,
+        columns: impl Into<ProjectionColumns>,
         filter: impl Into<Self::FilterType>,
-    ) -> Result<Vec<P>, rusqlite::Error> {
+    ) -> Result<Vec<P>, Error> {
         projections::project::<Self::RowType, P, Self::FilterType>(
             self.connection(),
+            self.table_name(),
             Projection::new(columns.into()),
             filter.into(),
         )
     }
+    /// Calls `callback` for every row matching the filter. The rows are read
+    /// one by one, so large results are never held in memory at once.
+    fn for_each(
+        &self,
+        filter: impl Into<Self::FilterType>,
+        callback: impl FnMut(Self::RowType),
+    ) -> Result<(), Error> {
+        for_each_where::<Self::RowType, Self::FilterType>(
+            &self.connection(),
+            self.table_name(),
+            filter,
+            callback,
+        )
+    }
+    /// Loads all rows matching the filter together with their rowid.
+    fn load_where_with_rowid(
+        &self,
+        filter: impl Into<Self::FilterType>,
+    ) -> Result<Vec<(i64, Self::RowType)>, Error> {
+        load_where_with_rowid::<Self::RowType, Self::FilterType>(
+            &self.connection(),
+            self.table_name(),
+            filter,
+        )
+    }
+    /// Loads the first row matching the filter, e.g. when looking up a row
+    /// by a unique column.
+    fn find_one(
+        &self,
+        filter: impl Into<Self::FilterType>,
+    ) -> Result<Option<Self::RowType>, Error> {
+        find_one::<Self::RowType, Self::FilterType>(&self.connection(), self.table_name(), filter)
+    }
+    /// Loads all rows matching the filter and the SQL condition `extra_sql`,
+    /// e.g. `"LENGTH(title) > ?"`, for conditions the filter cannot express.
+    /// The `?` placeholders are bound to `params`.
+    ///
+    /// `extra_sql` is inserted into the query as is. Never build it from
+    /// untrusted input, pass such values as `params` instead.
+    fn filter_where(
+        &self,
+        filter: impl Into<Self::FilterType>,
+        extra_sql: &str,
+        params: &[&dyn rusqlite::ToSql],
+    ) -> Result<Vec<Self::RowType>, Error> {
+        load_where_sql::<Self::RowType, Self::FilterType>(
+            &self.connection(),
+            self.table_name(),
+            filter,
+            extra_sql,
+            params,
+        )
+    }
+    /// Loads all rows of the table keyed by their primary key, e.g. to fill
+    /// an in-memory cache.
+    fn load_map(
+        &self,
+    ) -> Result<HashMap<<Self::RowType as HasPrimaryKey>::PrimaryKey, Self::RowType>, Error>
+    where
+        Self::RowType: HasPrimaryKey,
+        <Self::RowType as HasPrimaryKey>::PrimaryKey: Hash + Eq + Clone,
+        Self::FilterType: Default,
+    {
+        Ok(self
+            .load_where(Self::FilterType::default())?
+            .into_iter()
+            .map(|row| (row.primary_key().clone(), row))
+            .collect())
+    }
+    /// Loads page `page` of the rows matching the filter, starting at 0, and
+    /// counts all matching rows, e.g. to show the number of pages.
+    fn paginate(
+        &self,
+        filter: impl Into<Self::FilterType>,
+        page: usize,
+        page_size: usize,
+    ) -> Result<Page<Self::RowType>, Error> {
+        paginate::<Self::RowType, Self::FilterType>(
+            &self.connection(),
+            self.table_name(),
+            filter,
+            page,
+            page_size,
+        )
+    }
+    /// Loads the rows matching the filter, lets `f` change them and writes the
+    /// rows, for which it returns true, back. Either all changes are stored
+    /// or none. Returns how many rows were written.
+    fn update_each(
+        &self,
+        filter: impl Into<Self::FilterType>,
+        f: impl FnMut(&mut Self::RowType) -> bool,
+    ) -> Result<usize, Error> {
+        update_each::<Self::RowType, Self::FilterType>(
+            &self.connection(),
+            self.table_name(),
+            filter,
+            f,
+        )
+    }
+    /// Deletes all rows of the table and returns how many were deleted.
+    fn clear(&self) -> Result<usize, Error> {
+        clear_table::<Self::RowType>(self.connection(), self.table_name())
+    }
+    /// Deletes all rows matching the filter and returns how many were deleted.
+    fn delete(&self, filter: impl Into<Self::FilterType>) -> Result<usize, Error> {
+        delete::<Self::RowType, Self::FilterType>(self.connection(), self.table_name(), filter)
+    }
+    /// Deletes all rows matching the filter and returns them. Either all rows
+    /// are deleted or none.
+    fn delete_returning(
+        &self,
+        filter: impl Into<Self::FilterType>,
+    ) -> Result<Vec<Self::RowType>, Error> {
+        delete_returning::<Self::RowType, Self::FilterType>(
+            &self.connection(),
+            self.table_name(),
+            filter,
+        )
+    }
+    /// Returns whether any row matches the filter, without loading it.
+    fn exists(&self, filter: impl Into<Self::FilterType>) -> Result<bool, Error> {
+        exists::<Self::RowType, Self::FilterType>(self.connection(), self.table_name(), filter)
+    }
+    /// Loads a single column of the row matching `key`. Returns
+    /// `Error::MissingColumn` if the table has no such column.
+    fn get_field<V: Projectable>(
+        &self,
+        key: impl Into<Self::FilterType>,
+        column: impl Into<Cow<'static, str>>,
+    ) -> Result<Option<V>, Error> {
+        let column = column.into();
+        ensure_column::<Self::RowType>(&column)?;
+        Ok(projections::project::<Self::RowType, V, Self::FilterType>(
+            self.connection(),
+            self.table_name(),
+            Projection::new(column.into()).with_limit(1),
+            key.into(),
+        )?
+        .into_iter()
+        .next())
+    }
+    /// Adds `delta` to a numeric column of all rows matching the filter in a
+    /// single statement, so concurrent increments are not lost.
+    fn increment(
+        &self,
+        filter: impl Into<Self::FilterType>,
+        column: impl Into<Cow<'static, str>>,
+        delta: impl Into<rusqlite::types::Value>,
+    ) -> Result<usize, Error> {
+        let column = column.into();
+        let value = expr::Expr::column(column.clone()) + expr::Expr::literal(delta);
+        self.update_expr(filter, column, value)
+    }
+    /// Sets a column of all rows matching the filter to the value of an
+    /// expression, which may reference columns of the same row.
+    fn update_expr(
+        &self,
+        filter: impl Into<Self::FilterType>,
+        column: impl Into<Cow<'static, str>>,
+        value: expr::Expr,
+    ) -> Result<usize, Error> {
+        update_expr::<Self::RowType, Self::FilterType>(
+            self.connection(),
+            self.table_name(),
+            filter,
+            &column.into(),
+            &value,
+        )
+    }
     fn project_distinct<P: Projectable>(
         &self,
         columns: impl Into<ProjectionColumns>,
         filter: impl Into<Self::FilterType>,
-    ) -> Result<Vec<P>, rusqlite::Error> {
+    ) -> Result<Vec<P>, Error> {
         projections::project::<Self::RowType, P, Self::FilterType>(
             self.connection(),
+            self.table_name(),
             Projection::new(columns.into()).with_distinct(true),
             filter.into(),
         )
     }
+    /// Loads all rows matching the filter in the given order. Unlike
+    /// [`Self::load_where`], the filter is never split, so its IN lists have to
+    /// fit in the variable limit of the connection. Longer ones fail with
+    /// [`Error::TooManyParameters`].
+    fn load_where_ordered(
+        &self,
+        filter: impl Into<Self::FilterType>,
+        order: &GenericOrder,
+    ) -> Result<Vec<Self::RowType>, Error> {
+        load_where_ordered::<Self::RowType, Self::FilterType>(
+            &self.connection(),
+            self.table_name(),
+            filter,
+            order,
+        )
+    }
+    /// Loads the distinct values of a single column of all rows matching the
+    /// filter. Returns `Error::MissingColumn` if the table has no such column.
+    fn distinct_values<V: Projectable>(
+        &self,
+        column: impl Into<Cow<'static, str>>,
+        filter: impl Into<Self::FilterType>,
+    ) -> Result<Vec<V>, Error> {
+        let column = column.into();
+        ensure_column::<Self::RowType>(&column)?;
+        self.project_distinct(column, filter)
+    }
+    /// Computes an aggregate over a numeric column of all rows matching the
+    /// filter. Returns `None` if no row has a value in this column.
+    fn aggregate(
+        &self,
+        column: impl Into<Cow<'static, str>>,
+        aggregate: Aggregate,
+        filter: impl Into<Self::FilterType>,
+    ) -> Result<Option<f64>, Error> {
+        self::aggregate::<Self::RowType, Self::FilterType>(
+            self.connection(),
+            self.table_name(),
+            &column.into(),
+            aggregate,
+            filter,
+        )
+    }
+    /// Counts the distinct values of a single column over the whole table.
+    /// NULL is not counted.
+    fn count_distinct(&self, column: impl Into<Cow<'static, str>>) -> Result<usize, Error> {
+        count_distinct::<Self::RowType>(self.connection(), self.table_name(), &column.into())
+    }
     // fn count(
     //     &self,
     //     filter: <Self::RowType as HasFilter>::Filter,
-    // ) -> Result<usize, rusqlite::Error> {
+    // ) -> Result<usize, Error> {
     //     Ok(self.filter(filter)?.len())
     // }
-    // fn migrate(&self, actual_columns: &[SqlColumn]) -> Result<(), rusqlite::Error>;
-    // fn drain(
-    //     &self,
-    //     mut callback: impl FnMut(&Self::ValueType) -> bool,
-    // ) -> Result<Vec<Self::ValueType>, rusqlite::Error> {
-    //     Ok(self
-    //         .filter(Default::default())?
-    //         .into_iter()
-    //         .filter_map(|r| {
-    //             if (callback)(&r) {
-    //                 let filter = <Self::ValueType as MustBeEqual<
-    //                     <Self::RowType as HasFilter>::Filter,
-    //                 >>::must_be_equal(&r);
-    //                 self.delete(filter).ok()?;
-    //                 Some(r)
-    //             } else {
-    //                 None
-    //             }
-    //         })
-    //         .collect::<Vec<_>>())
-    // }
+    // fn migrate(&self, actual_columns: &[SqlColumn]) -> Result<(), Error>;
+    /// Removes the rows matching the filter, for which the callback returns
+    /// true, and returns them. Rows are deleted by their rowid, so of two
+    /// identical rows only the accepted one is removed.
+    fn drain(
+        &self,
+        filter: impl Into<Self::FilterType>,
+        mut callback: impl FnMut(&Self::RowType) -> bool,
+    ) -> Result<Vec<Self::RowType>, Error> {
+        let (rowids, rows): (Vec<_>, Vec<_>) = self
+            .load_where_with_rowid(filter)?
+            .into_iter()
+            .filter(|(_, r)| callback(r))
+            .unzip();
+        delete_rowids(
+            self.connection(),
+            self.table_name(),
+            Self::RowType::IS_SOFT_DELETE,
+            &rowids,
+        )?;
+        Ok(rows)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -645,55 +1927,133 @@ pub struct Ordering {
     pub nulls: Option<OrderingNulls>,
 }
 
-// #[derive(Debug, Default)]
-// pub struct GenericOrder {
-//     pub columns: Vec<(&'static str, Ordering)>,
-// }
-
-// impl GenericOrder {
-//     fn to_sql(&self) -> String {
-//         if self.columns.is_empty() {
-//             return String::new();
-//         }
-//         let mut result: String = "ORDER BY".into();
-//         for (i, (column, ordering)) in self.columns.iter().enumerate() {
-//             if i > 0 {
-//                 result.push(',');
-//             }
-//             result.push(' ');
-//             result.push_str(column);
-//             match ordering.asc_desc {
-//                 Some(OrderingAscDesc::Ascending) => {
-//                     result.push(' ');
-//                     result.push_str("ASC");
-//                 }
-//                 Some(OrderingAscDesc::Descending) => {
-//                     result.push(' ');
-//                     result.push_str("DESC");
-//                 }
-//                 None => {}
-//             }
-//             match ordering.nulls {
-//                 Some(OrderingNulls::NullsFirst) => {
-//                     result.push(' ');
-//                     result.push_str("NULLS FIRST");
-//                 }
-//                 Some(OrderingNulls::NullsLast) => {
-//                     result.push(' ');
-//                     result.push_str("NULLS LAST");
-//                 }
-//                 None => {}
-//             }
-//         }
-//         result
-//     }
-// }
-
-// impl GenericOrder {
-//     pub fn add(&mut self, column: &'static str, order: Ordering) {
-//         self.columns.push((column, order));
-//     }
-// }
+/// The order of loaded rows. Columns of nested types use their prefixed
+/// names, which [`column_name_of`] builds.
+///
+/// ```rust
+///# use silo::{GenericOrder, column_name_of, derive::{ToColumns, ToTable}};
+///# #[derive(ToColumns)]
+///# struct Movie { title: String }
+///# #[derive(ToTable)]
+///# struct Rating { movie: Movie, stars: u8 }
+/// let order = GenericOrder::default()
+///     .desc(column_name_of!(Rating, stars))
+///     .asc(column_name_of!(Rating, movie.title));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct GenericOrder {
+    pub columns: Vec<(Cow<'static, str>, Ordering)>,
+}
+
+impl GenericOrder {
+    /// Starts an order by `column`, which is refined with the methods of
+    /// [`GenericOrderBuilder`].
+    ///
+    /// ```rust
+    ///# use silo::GenericOrder;
+    /// let order: GenericOrder = GenericOrder::by("year")
+    ///     .desc()
+    ///     .nulls_last()
+    ///     .then("title")
+    ///     .asc()
+    ///     .into();
+    /// ```
+    pub fn by(column: impl Into<Cow<'static, str>>) -> GenericOrderBuilder {
+        GenericOrderBuilder {
+            order: Self::default().add(column, Ordering::default()),
+        }
+    }
+
+    pub fn add(mut self, column: impl Into<Cow<'static, str>>, order: Ordering) -> Self {
+        self.columns.push((column.into(), order));
+        self
+    }
+
+    pub fn asc(self, column: impl Into<Cow<'static, str>>) -> Self {
+        self.add(
+            column,
+            Ordering {
+                asc_desc: Some(OrderingAscDesc::Ascending),
+                nulls: None,
+            },
+        )
+    }
+
+    pub fn desc(self, column: impl Into<Cow<'static, str>>) -> Self {
+        self.add(
+            column,
+            Ordering {
+                asc_desc: Some(OrderingAscDesc::Descending),
+                nulls: None,
+            },
+        )
+    }
+
+    fn to_sql(&self, sql: &mut String) {
+        for (i, (column, ordering)) in self.columns.iter().enumerate() {
+            sql.push_str(if i == 0 { " ORDER BY " } else { ", " });
+            _ = write!(sql, "{}", Quoted(column));
+            match ordering.asc_desc {
+                Some(OrderingAscDesc::Ascending) => sql.push_str(" ASC"),
+                Some(OrderingAscDesc::Descending) => sql.push_str(" DESC"),
+                None => {}
+            }
+            match ordering.nulls {
+                Some(OrderingNulls::NullsFirst) => sql.push_str(" NULLS FIRST"),
+                Some(OrderingNulls::NullsLast) => sql.push_str(" NULLS LAST"),
+                None => {}
+            }
+        }
+    }
+}
+
+/// Builds a [`GenericOrder`], every method except [`Self::then`] changes how
+/// the last added column is ordered.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenericOrderBuilder {
+    order: GenericOrder,
+}
+
+impl GenericOrderBuilder {
+    pub fn asc(self) -> Self {
+        self.change_last(|o| o.asc_desc = Some(OrderingAscDesc::Ascending))
+    }
+
+    pub fn desc(self) -> Self {
+        self.change_last(|o| o.asc_desc = Some(OrderingAscDesc::Descending))
+    }
+
+    pub fn nulls_first(self) -> Self {
+        self.change_last(|o| o.nulls = Some(OrderingNulls::NullsFirst))
+    }
+
+    pub fn nulls_last(self) -> Self {
+        self.change_last(|o| o.nulls = Some(OrderingNulls::NullsLast))
+    }
+
+    /// Orders rows, which are equal in all previous columns, by `column`.
+    pub fn then(mut self, column: impl Into<Cow<'static, str>>) -> Self {
+        self.order = self.order.add(column, Ordering::default());
+        self
+    }
+
+    pub fn build(self) -> GenericOrder {
+        self.order
+    }
+
+    fn change_last(mut self, change: impl FnOnce(&mut Ordering)) -> Self {
+        if let Some((_, ordering)) = self.order.columns.last_mut() {
+            change(ordering);
+        }
+        self
+    }
+}
+
+impl From<GenericOrderBuilder> for GenericOrder {
+    fn from(value: GenericOrderBuilder) -> Self {
+        value.build()
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SqlColumn {
@@ -701,6 +2061,46 @@ pub struct SqlColumn {
     pub r#type: SqlColumnType,
     pub is_primary: bool,
     pub is_unique: bool,
+    /// Set by `#[silo(index)]`, the column gets its own index.
+    pub is_indexed: bool,
+    /// Set by `#[silo(unique_index)]`, the column gets a unique index, which
+    /// only covers the rows where it is not NULL.
+    pub is_unique_indexed: bool,
+    /// Set by `#[silo(nocase)]`, the column compares ASCII letters case
+    /// insensitively, also for its unique constraint and indices.
+    pub is_nocase: bool,
+    /// Set by `#[silo(default = <lit>)]`, the literal as sql. Missing columns
+    /// with a default are added to existing tables on load.
+    pub default: Option<Cow<'static, str>>,
+    /// Set by `#[silo(references = <table>)]`, the name of the table, whose
+    /// primary key this column references.
+    pub references: Option<Cow<'static, str>>,
+    /// Set by `#[silo(skip_insert)]`, the column is left out of inserts, so
+    /// new rows get its default.
+    pub is_skip_insert: bool,
+    /// Set by `#[silo(autoincrement)]` on an integer primary key. SQLite
+    /// assigns the value and never reuses it, so it is left out of inserts.
+    pub is_autoincrement: bool,
+    /// Set by `#[silo(skip_select)]`, the column is never read, so loaded
+    /// rows do not hold its value.
+    pub is_skip_select: bool,
+    /// A condition, which every value of the column has to satisfy, as sql,
+    /// e.g. `"done" IN (0, 1)` for a bool. Set by `#[silo(check = "..")]` as
+    /// well, then both conditions have to hold.
+    pub check: Option<Cow<'static, str>>,
+}
+
+/// Adds the condition of `#[silo(check = "..")]` to the condition of a
+/// column, used by the derive.
+#[doc(hidden)]
+pub fn and_check(
+    check: Option<Cow<'static, str>>,
+    other: Option<&'static str>,
+) -> Option<Cow<'static, str>> {
+    match (check, other) {
+        (Some(check), Some(other)) => Some(format!("({check}) AND ({other})").into()),
+        (check, other) => check.or(other.map(Cow::Borrowed)),
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -719,11 +2119,6 @@ pub enum SqlColumnType {
 impl SqlColumnType {
     pub fn as_sql(&self) -> &'static str {
         match self {
-            // TODO: Find good handling for optional values!
-            // SqlColumnType::Float => "REAL NOT NULL",
-            // SqlColumnType::Integer => "INTEGER NOT NULL",
-            // SqlColumnType::Text => "TEXT NOT NULL",
-            // SqlColumnType::Blob => "BLOB NOT NULL",
             SqlColumnType::OptionalFloat | Self::Float => "REAL",
             SqlColumnType::OptionalInteger | Self::Integer => "INTEGER",
             SqlColumnType::OptionalText | Self::Text => "TEXT",
@@ -732,7 +2127,29 @@ impl SqlColumnType {
         }
     }
 
-    #[allow(dead_code)]
+    /// Like [`Self::as_sql`], but STRICT tables only allow columns of type
+    /// `INT`, `INTEGER`, `REAL`, `TEXT`, `BLOB` and `ANY`, so untyped columns
+    /// are declared as `ANY` there.
+    pub fn as_strict_sql(&self, is_strict: bool) -> &'static str {
+        match self {
+            SqlColumnType::Null if is_strict => "ANY",
+            _ => self.as_sql(),
+        }
+    }
+
+    /// Whether columns of this type are declared `NOT NULL`. Floats are
+    /// always nullable, since SQLite stores NaN as NULL.
+    pub fn is_not_null(&self) -> bool {
+        matches!(self, Self::Integer | Self::Text | Self::Blob)
+    }
+
+    pub fn is_numeric(&self) -> bool {
+        matches!(
+            self,
+            Self::Float | Self::Integer | Self::OptionalFloat | Self::OptionalInteger
+        )
+    }
+
     const fn to_optional(this: SqlColumnType) -> SqlColumnType {
         match this {
             SqlColumnType::OptionalFloat | SqlColumnType::Float => Self::OptionalFloat,
@@ -744,12 +2161,10 @@ impl SqlColumnType {
     }
 }
 
-pub fn insert_into_table<'a, T: ToTable<'a> + Clone>(
-    connection: &&'a rusqlite::Connection,
-    value: T,
-) -> Result<bool, rusqlite::Error> {
+fn insert_sql<T: AsColumns>(table: TableName<'_>, behavior: SqlFailureBehavior) -> String {
     let columns = T::columns(None, false, false)
         .into_iter()
+        .filter(|c| !c.is_skip_insert)
         .map(|c| c.name)
         .fold(String::new(), |mut acc, cur| {
             if acc.is_empty() {
@@ -762,7 +2177,11 @@ pub fn insert_into_table<'a, T: ToTable<'a> + Clone>(
                 acc
             }
         });
-    let values = (0..T::COLUMN_COUNT)
+    let inserted_column_count = T::columns(None, false, false)
+        .iter()
+        .filter(|c| !c.is_skip_insert)
+        .count();
+    let values = (0..inserted_column_count)
         .map(|v| v + 1)
         .fold(String::new(), |mut acc, cur| {
             if acc.is_empty() {
@@ -774,51 +2193,885 @@ pub fn insert_into_table<'a, T: ToTable<'a> + Clone>(
             }
         });
 
-    let sql = format!("INSERT INTO \"{}\" ({columns}) VALUES ({values})", T::NAME,);
-    debug_sql(&sql);
+    if columns.is_empty() {
+        return format!("INSERT OR {behavior} INTO {table} DEFAULT VALUES");
+    }
+    format!("INSERT OR {behavior} INTO {table} ({columns}) VALUES ({values})")
+}
+
+/// Whether replacing rows of `T` updates the row conflicting on
+/// [`ToTable::REPLACE_CONFLICT_COLUMNS`] instead.
+fn replaces_on_conflict_columns<'a, T: ToTable<'a>>(behavior: SqlFailureBehavior) -> bool {
+    behavior == SqlFailureBehavior::Replace && !T::REPLACE_CONFLICT_COLUMNS.is_empty()
+}
 
-    let mut stmt = connection.prepare(&sql)?;
+/// Like `insert_sql`, but for a table with `#[silo(replace(..))]` replacing
+/// is an upsert on its conflict columns, so other conflicts abort the insert.
+fn table_insert_sql<'a, T: ToTable<'a>>(
+    table: TableName<'_>,
+    behavior: SqlFailureBehavior,
+) -> Result<String, Error> {
+    if replaces_on_conflict_columns::<T>(behavior) {
+        upsert_sql::<T>(table, T::REPLACE_CONFLICT_COLUMNS)
+    } else {
+        Ok(insert_sql::<T>(table, behavior))
+    }
+}
+
+/// Executes an insert statement, returns false if a constraint was violated.
+fn execute_insert<T: AsParams + AsColumns>(
+    connection: &rusqlite::Connection,
+    sql: &str,
+    value: &T,
+) -> Result<bool, Error> {
+    debug_sql(sql);
+
+    let mut stmt = connection.prepare_cached(sql)?;
     let params = value.as_params();
-    let params: Vec<_> = params.iter().map(|p| p.as_dyn()).collect();
+    let params = inserted_params::<T>(&params);
     match stmt.execute(params.as_slice()) {
-        Ok(_) => Ok(true),
-        Err(rusqlite::Error::SqliteFailure(
+        // Ignored rows are not inserted, but do not fail either.
+        Ok(changed) => Ok(changed > 0),
+        Err(e) if is_constraint_violation(&e) => Ok(false),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Keeps the parameters in line with the columns of `insert_sql`.
+fn inserted_params<'b, T: AsColumns>(params: &'b [ToSqlDyn<'b>]) -> Vec<&'b dyn rusqlite::ToSql> {
+    params
+        .iter()
+        .zip(T::columns(None, false, false))
+        .filter(|(_, c)| !c.is_skip_insert)
+        .map(|(p, _)| p.as_dyn())
+        .collect()
+}
+
+fn is_constraint_violation(error: &rusqlite::Error) -> bool {
+    matches!(
+        error,
+        rusqlite::Error::SqliteFailure(
             rusqlite::ffi::Error {
                 code: ErrorCode::ConstraintViolation,
                 ..
             },
             _,
-        )) => Ok(false),
-        Err(e) => Err(e),
+        )
+    )
+}
+
+pub fn insert_into_table<'a, T: ToTable<'a> + Clone>(
+    connection: &&'a rusqlite::Connection,
+    table: TableName<'_>,
+    value: T,
+) -> Result<bool, Error> {
+    insert_into_table_with(connection, table, value, SqlFailureBehavior::Abort)
+}
+
+pub fn insert_into_table_with<'a, T: ToTable<'a>>(
+    connection: &&'a rusqlite::Connection,
+    table: TableName<'_>,
+    value: T,
+    behavior: SqlFailureBehavior,
+) -> Result<bool, Error> {
+    execute_insert(connection, &table_insert_sql::<T>(table, behavior)?, &value)
+}
+
+/// Inserts only the columns of `value`, like the struct generated by
+/// `#[silo(generate_insert_struct)]`. The other columns are assigned by the
+/// database. Replacing follows `#[silo(replace(..))]` of `T`, like
+/// [`insert_into_table_with`].
+pub fn insert_new<'a, T: ToTable<'a>, V: AsParams + AsColumns>(
+    connection: &rusqlite::Connection,
+    table: TableName<'_>,
+    value: &V,
+    behavior: SqlFailureBehavior,
+) -> Result<bool, Error> {
+    let sql = if replaces_on_conflict_columns::<T>(behavior) {
+        on_conflict_update_sql::<V>(table, T::REPLACE_CONFLICT_COLUMNS)
+    } else {
+        insert_sql::<V>(table, behavior)
+    };
+    execute_insert(connection, &sql, value)
+}
+
+/// Inserts the row and returns its rowid, or None if no row was inserted.
+pub fn insert_returning_id<'a, T: ToTable<'a>>(
+    connection: &&'a rusqlite::Connection,
+    table: TableName<'_>,
+    value: T,
+    behavior: SqlFailureBehavior,
+) -> Result<Option<i64>, Error> {
+    if replaces_on_conflict_columns::<T>(behavior) {
+        // Updating the conflicting row does not change last_insert_rowid.
+        let sql = format!(
+            "{} RETURNING rowid",
+            table_insert_sql::<T>(table, behavior)?
+        );
+        debug_sql(&sql);
+        let mut s = connection.prepare_cached(&sql)?;
+        let params = value.as_params();
+        let params = inserted_params::<T>(&params);
+        let mut rows = s.query(params.as_slice())?;
+        return match rows.next() {
+            Ok(Some(row)) => Ok(Some(row.get(0)?)),
+            Ok(None) => Ok(None),
+            Err(e) if is_constraint_violation(&e) => Ok(None),
+            Err(e) => Err(e.into()),
+        };
+    }
+    if execute_insert(connection, &insert_sql::<T>(table, behavior), &value)? {
+        Ok(Some(connection.last_insert_rowid()))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Inserts the row and reads it back, including the values assigned by the
+/// database, like defaults and autoincrement ids. Returns None if no row was
+/// inserted.
+pub fn insert_returning<'a, T: ToTable<'a>>(
+    connection: &&'a rusqlite::Connection,
+    table: TableName<'_>,
+    value: T,
+    behavior: SqlFailureBehavior,
+) -> Result<Option<T>, Error> {
+    // RETURNING needs SQLite 3.35, older versions select the row by its rowid.
+    if rusqlite::version_number() < 3_035_000 {
+        let Some(rowid) = insert_returning_id(connection, table, value, behavior)? else {
+            return Ok(None);
+        };
+        let sql = format!("SELECT * FROM {table} WHERE rowid = ?1");
+        debug_sql(&sql);
+        let mut s = connection.prepare_cached(&sql)?;
+        let mut rows = s.query([rowid])?;
+        return match rows.next()? {
+            Some(row) => Ok(Some(T::try_from_row(row, connection)?)),
+            None => Ok(None),
+        };
+    }
+    let sql = format!("{} RETURNING *", table_insert_sql::<T>(table, behavior)?);
+    debug_sql(&sql);
+    let mut s = connection.prepare_cached(&sql)?;
+    let params = value.as_params();
+    let params = inserted_params::<T>(&params);
+    let mut rows = s.query(params.as_slice())?;
+    match rows.next() {
+        Ok(Some(row)) => Ok(Some(T::try_from_row(row, connection)?)),
+        Ok(None) => Ok(None),
+        Err(e) if is_constraint_violation(&e) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Deletes all rows of the table of `T` and returns how many were deleted.
+pub fn clear_table<'a, T: ToTable<'a>>(
+    connection: &rusqlite::Connection,
+    table: TableName<'_>,
+) -> Result<usize, Error> {
+    let sql = if T::IS_SOFT_DELETE {
+        format!(
+            "{} WHERE \"{SOFT_DELETE_COLUMN}\" IS NULL",
+            delete_sql(table, true)
+        )
+    } else {
+        delete_sql(table, false)
+    };
+    Ok(execute(connection, &sql, ())?)
+}
+
+/// Starts the statement deleting rows of `table`. Rows of a soft delete table
+/// are only marked as deleted with the current time.
+fn delete_sql(table: TableName<'_>, is_soft_delete: bool) -> String {
+    if is_soft_delete {
+        format!(
+            "UPDATE {table} SET \"{SOFT_DELETE_COLUMN}\" = strftime('%Y-%m-%d %H:%M:%f+00:00', 'now')"
+        )
+    } else {
+        format!("DELETE FROM {table}")
+    }
+}
+
+pub fn delete<'a, T: ToTable<'a>, F: filter::Filter>(
+    connection: &rusqlite::Connection,
+    table: TableName<'_>,
+    filter: impl Into<F>,
+) -> Result<usize, Error> {
+    let mut deleted = 0;
+    for_each_deleted::<F>(connection, table, T::IS_SOFT_DELETE, filter.into(), |_| {
+        deleted += 1;
+        Ok(())
+    })?;
+    Ok(deleted)
+}
+
+pub fn delete_returning<'a, T: ToTable<'a>, F: filter::Filter>(
+    connection: &&'a rusqlite::Connection,
+    table: TableName<'_>,
+    filter: impl Into<F>,
+) -> Result<Vec<T>, Error> {
+    let mut result = Vec::new();
+    for_each_deleted::<F>(connection, table, T::IS_SOFT_DELETE, filter.into(), |r| {
+        result.push(T::try_from_row(r, connection)?);
+        Ok(())
+    })?;
+    Ok(result)
+}
+
+/// Deletes the rows with the given rowids inside of a savepoint.
+fn delete_rowids(
+    connection: &rusqlite::Connection,
+    table: TableName<'_>,
+    is_soft_delete: bool,
+    rowids: &[i64],
+) -> Result<usize, Error> {
+    let limit = variable_limit(connection)?.max(1);
+    execute(connection, "SAVEPOINT silo_delete", ())?;
+    let result = rowids.chunks(limit).try_fold(0, |deleted, chunk| {
+        let sql = format!(
+            "{} WHERE rowid IN ({})",
+            delete_sql(table, is_soft_delete),
+            vec!["?"; chunk.len()].join(", ")
+        );
+        debug_sql(&sql);
+        let mut s = connection.prepare_cached(&sql)?;
+        Ok(deleted + s.execute(rusqlite::params_from_iter(chunk))?)
+    });
+    if result.is_err() {
+        execute(connection, "ROLLBACK TO silo_delete", ())?;
+    }
+    execute(connection, "RELEASE silo_delete", ())?;
+    result
+}
+
+/// Runs `DELETE FROM {table} WHERE {filter} RETURNING *` and calls the
+/// callback with every deleted row. A split filter is deleted inside of a
+/// savepoint, so either every part is deleted or none.
+fn for_each_deleted<F: filter::Filter>(
+    connection: &rusqlite::Connection,
+    table: TableName<'_>,
+    is_soft_delete: bool,
+    filter: F,
+    mut callback: impl FnMut(&rusqlite::Row) -> Result<(), Error>,
+) -> Result<(), Error> {
+    execute(connection, "SAVEPOINT silo_delete", ())?;
+    let result = split_filter(connection, filter, 0).and_then(|filters| {
+        for filter in filters {
+            let mut sql = format!("{} WHERE ", delete_sql(table, is_soft_delete));
+            filter.to_sql(&mut sql, None);
+            let mut sql = sql.trim_end_matches(" WHERE ").to_string();
+            sql.push_str(" RETURNING *");
+            debug_sql(&sql);
+            let mut s = connection.prepare_cached(&sql)?;
+            let params = filter.as_params();
+            let params: Vec<_> = params.iter().map(|p| p.as_dyn()).collect();
+
+            let mut rows = s.query(params.as_slice())?;
+            while let Some(row) = rows.next()? {
+                callback(row)?;
+            }
+        }
+        Ok(())
+    });
+    if result.is_err() {
+        execute(connection, "ROLLBACK TO silo_delete", ())?;
     }
+    execute(connection, "RELEASE silo_delete", ())?;
+    result
+}
+
+/// Runs the insert of `value` inside of a savepoint, which is rolled back
+/// afterwards. Returns whether the insert would have succeeded, without
+/// changing the database.
+pub fn validate_insert<'a, T: ToTable<'a>>(
+    connection: &rusqlite::Connection,
+    table: TableName<'_>,
+    value: &T,
+) -> Result<bool, Error> {
+    execute(connection, "SAVEPOINT silo_validate_insert", ())?;
+    let result = execute_insert(
+        connection,
+        &insert_sql::<T>(table, SqlFailureBehavior::Abort),
+        value,
+    );
+    execute(connection, "ROLLBACK TO silo_validate_insert", ())?;
+    execute(connection, "RELEASE silo_validate_insert", ())?;
+    result
+}
+
+/// Inserts the row or, if it conflicts with an existing row on
+/// `conflict_columns`, updates all other columns of the existing row. An empty
+/// slice of conflict columns uses the primary key.
+pub fn upsert<'a, T: ToTable<'a>>(
+    connection: &rusqlite::Connection,
+    table: TableName<'_>,
+    value: T,
+    conflict_columns: &[&str],
+) -> Result<bool, Error> {
+    execute_insert(
+        connection,
+        &upsert_sql::<T>(table, conflict_columns)?,
+        &value,
+    )
+}
+
+/// Returns `Error::MissingConflictColumns`, if no conflict columns are given
+/// and the table has no primary key.
+fn upsert_sql<'a, T: ToTable<'a>>(
+    table: TableName<'_>,
+    conflict_columns: &[&str],
+) -> Result<String, Error> {
+    let columns = T::columns(None, false, false);
+    let conflict_columns: Vec<&str> = if conflict_columns.is_empty() {
+        columns
+            .iter()
+            .filter(|c| c.is_primary)
+            .map(|c| c.name.as_ref())
+            .collect()
+    } else {
+        for column in conflict_columns {
+            ensure_column::<T>(column)?;
+        }
+        conflict_columns.to_vec()
+    };
+    if conflict_columns.is_empty() {
+        return Err(Error::MissingConflictColumns(table.name.to_string().into()));
+    }
+    Ok(on_conflict_update_sql::<T>(table, &conflict_columns))
+}
+
+/// Inserts the columns of `V`. A conflict on `conflict_columns` updates the
+/// other inserted columns of the existing row instead.
+fn on_conflict_update_sql<V: AsColumns>(table: TableName<'_>, conflict_columns: &[&str]) -> String {
+    let columns = V::columns(None, false, false);
+    let updated_columns = columns
+        .iter()
+        .filter(|c| !c.is_skip_insert && !conflict_columns.contains(&c.name.as_ref()))
+        .map(|c| format!("\"{0}\" = excluded.\"{0}\"", c.name))
+        .collect::<Vec<_>>();
+    let conflict_columns = conflict_columns
+        .iter()
+        .map(|c| format!("\"{c}\""))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut sql = insert_sql::<V>(table, SqlFailureBehavior::Abort);
+    if updated_columns.is_empty() {
+        _ = write!(sql, " ON CONFLICT({conflict_columns}) DO NOTHING");
+    } else {
+        _ = write!(
+            sql,
+            " ON CONFLICT({conflict_columns}) DO UPDATE SET {}",
+            updated_columns.join(", ")
+        );
+    }
+    sql
+}
+
+fn variable_limit(connection: &rusqlite::Connection) -> Result<usize, Error> {
+    Ok(connection.limit(Limit::SQLITE_LIMIT_VARIABLE_NUMBER)? as usize)
+}
+
+/// Returns `Error::TooManyParameters` for a statement, which cannot be split
+/// and binds more parameters than the variable limit of the connection.
+fn ensure_within_variable_limit(
+    connection: &rusqlite::Connection,
+    param_count: usize,
+) -> Result<(), Error> {
+    let limit = variable_limit(connection)?;
+    if param_count > limit {
+        return Err(Error::TooManyParameters(param_count, limit));
+    }
+    Ok(())
+}
+
+/// Splits the filter, so that every part binds at most the variable limit of
+/// the connection minus `reserved_params` parameters.
+fn split_filter<F: filter::Filter>(
+    connection: &rusqlite::Connection,
+    filter: F,
+    reserved_params: usize,
+) -> Result<Vec<F>, Error> {
+    let limit = variable_limit(connection)?.saturating_sub(reserved_params);
+    if filter.as_params().len() <= limit {
+        return Ok(vec![filter]);
+    }
+    // With multiple IN lists, every part binds parameters of all of them, so
+    // shrink the lists until the parts fit.
+    let mut max_params = limit;
+    loop {
+        let parts = filter.clone().split_in_lists(max_params);
+        if max_params <= 1 || parts.iter().all(|p| p.as_params().len() <= limit) {
+            return Ok(parts);
+        }
+        max_params /= 2;
+    }
+}
+
+/// Runs `SELECT {columns} FROM {table} WHERE {filter}` and calls `callback`
+/// for every row, while the rows are read.
+fn for_each_selected<F: filter::Filter>(
+    connection: &rusqlite::Connection,
+    table: TableName<'_>,
+    columns: &str,
+    filter: F,
+    mut callback: impl FnMut(&rusqlite::Row) -> Result<(), Error>,
+) -> Result<(), Error> {
+    for filter in split_filter(connection, filter, 0)? {
+        let mut sql = format!("SELECT {columns} FROM {table} WHERE ");
+        filter.to_sql(&mut sql, None);
+        let sql = sql.trim_end_matches(" WHERE ");
+        debug_sql(sql);
+        let mut s = connection.prepare_cached(sql)?;
+        let params = filter.as_params();
+        let params: Vec<_> = params.iter().map(|p| p.as_dyn()).collect();
+
+        let mut rows = s.query(params.as_slice())?;
+        while let Some(row) = rows.next()? {
+            callback(row)?;
+        }
+    }
+    Ok(())
+}
+
+/// Runs `SELECT {columns} FROM {table} WHERE {filter}` and maps every row.
+fn select_where<F: filter::Filter, R>(
+    connection: &rusqlite::Connection,
+    table: TableName<'_>,
+    columns: &str,
+    filter: F,
+    mut map: impl FnMut(&rusqlite::Row) -> Result<R, Error>,
+) -> Result<Vec<R>, Error> {
+    let mut result = Vec::new();
+    for_each_selected::<F>(connection, table, columns, filter, |r| {
+        result.push(map(r)?);
+        Ok(())
+    })?;
+    Ok(result)
 }
 
 pub fn load_where<'a, T: ToTable<'a>, F: filter::Filter>(
     connection: &&'a rusqlite::Connection,
+    table: TableName<'_>,
+    filter: impl Into<F>,
+) -> Result<Vec<T>, Error> {
+    select_where::<F, T>(connection, table, "*", filter.into(), |r| {
+        T::try_from_row(r, connection)
+    })
+}
+
+pub fn load_where_ordered<'a, T: ToTable<'a>, F: filter::Filter>(
+    connection: &&'a rusqlite::Connection,
+    table: TableName<'_>,
     filter: impl Into<F>,
-) -> Result<Vec<T>, rusqlite::Error> {
-    let mut sql = format!("SELECT * FROM \"{}\" WHERE ", T::NAME);
+    order: &GenericOrder,
+) -> Result<Vec<T>, Error> {
+    for (column, _) in &order.columns {
+        ensure_column::<T>(column)?;
+    }
     let filter = filter.into();
+    let params = filter.as_params();
+    ensure_within_variable_limit(connection, params.len())?;
+    let mut sql = format!("SELECT * FROM {table} WHERE ");
     filter.to_sql(&mut sql, None);
-    let sql = sql.trim_end_matches(" WHERE ");
-    debug_sql(sql);
-    let mut s = connection.prepare(sql)?;
-    // TODO: Filters encode their params directly. We might wanna change that,
-    // but for now, this is not needed.
+    let mut sql = sql.trim_end_matches(" WHERE ").to_string();
+    order.to_sql(&mut sql);
+    debug_sql(&sql);
+    let mut s = connection.prepare_cached(&sql)?;
+    let params: Vec<_> = params.iter().map(|p| p.as_dyn()).collect();
 
-    // let params = filter.as_params();
-    // let params: Vec<_> = params.iter().map(|p| p.as_dyn()).collect();
+    let mut rows = s.query(params.as_slice())?;
+    let mut result = Vec::new();
+    while let Some(row) = rows.next()? {
+        result.push(T::try_from_row(row, connection)?);
+    }
+    Ok(result)
+}
 
-    s.query(())?
-        .mapped(|r| T::try_from_row(r, connection).map_err(|_| todo!()))
-        .collect()
+/// Loads the first row matching the filter. Only a single row is read from
+/// the database.
+pub fn find_one<'a, T: ToTable<'a>, F: filter::Filter>(
+    connection: &&'a rusqlite::Connection,
+    table: TableName<'_>,
+    filter: impl Into<F>,
+) -> Result<Option<T>, Error> {
+    for filter in split_filter(connection, filter.into(), 0)? {
+        let mut sql = format!("SELECT * FROM {table} WHERE ");
+        filter.to_sql(&mut sql, None);
+        let mut sql = sql.trim_end_matches(" WHERE ").to_string();
+        sql.push_str(" LIMIT 1");
+        debug_sql(&sql);
+        let mut s = connection.prepare_cached(&sql)?;
+        let params = filter.as_params();
+        let params: Vec<_> = params.iter().map(|p| p.as_dyn()).collect();
+
+        let mut rows = s.query(params.as_slice())?;
+        if let Some(row) = rows.next()? {
+            return Ok(Some(T::try_from_row(row, connection)?));
+        }
+    }
+    Ok(None)
+}
+
+/// Loads all rows matching the filter and the SQL condition `extra_sql`. The
+/// `?` placeholders in `extra_sql` are bound to `params`, after the parameters
+/// of the filter.
+///
+/// `extra_sql` is inserted into the query as is, so it must never contain
+/// values from untrusted input. Pass them as `params` instead.
+pub fn load_where_sql<'a, T: ToTable<'a>, F: filter::Filter>(
+    connection: &&'a rusqlite::Connection,
+    table: TableName<'_>,
+    filter: impl Into<F>,
+    extra_sql: &str,
+    params: &[&dyn rusqlite::ToSql],
+) -> Result<Vec<T>, Error> {
+    let mut result = Vec::new();
+    for filter in split_filter(connection, filter.into(), params.len())? {
+        let mut sql = format!("SELECT * FROM {table} WHERE ");
+        filter.to_sql(&mut sql, None);
+        if !sql.ends_with(" WHERE ") {
+            sql.push_str(" AND ");
+        }
+        _ = write!(sql, "({extra_sql})");
+        debug_sql(&sql);
+        let mut s = connection.prepare_cached(&sql)?;
+        let filter_params = filter.as_params();
+        let all_params: Vec<_> = filter_params
+            .iter()
+            .map(|p| p.as_dyn())
+            .chain(params.iter().copied())
+            .collect();
+
+        let mut rows = s.query(all_params.as_slice())?;
+        while let Some(row) = rows.next()? {
+            result.push(T::try_from_row(row, connection)?);
+        }
+    }
+    Ok(result)
+}
+
+/// A page of rows together with the number of all matching rows, see
+/// [`SqlTable::paginate`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// The number of rows matching the filter on all pages.
+    pub total: usize,
+    /// The index of this page, starting at 0.
+    pub page: usize,
+    pub page_size: usize,
+}
+
+impl<T> Page<T> {
+    /// The number of pages needed for all matching rows.
+    pub fn page_count(&self) -> usize {
+        self.total.div_ceil(self.page_size.max(1))
+    }
+}
+
+/// Loads the rows on page `page` of all rows matching the filter, where every
+/// page has `page_size` rows, and counts all matching rows. Rows are ordered
+/// by their rowid, so the pages do not overlap. Both queries run inside of a
+/// savepoint, so they see the same rows.
+///
+/// Unlike [`load_where`], the filter is not split at the variable limit of
+/// the connection, so long IN lists fail with [`Error::TooManyParameters`].
+pub fn paginate<'a, T: ToTable<'a>, F: filter::Filter>(
+    connection: &&'a rusqlite::Connection,
+    table: TableName<'_>,
+    filter: impl Into<F>,
+    page: usize,
+    page_size: usize,
+) -> Result<Page<T>, Error> {
+    let filter = filter.into();
+    let mut condition = String::from(" WHERE ");
+    filter.to_sql(&mut condition, None);
+    let condition = condition.trim_end_matches(" WHERE ");
+    let params = filter.as_params();
+    ensure_within_variable_limit(connection, params.len())?;
+    let params: Vec<_> = params.iter().map(|p| p.as_dyn()).collect();
+
+    execute(connection, "SAVEPOINT silo_paginate", ())?;
+    let result = (|| {
+        let sql = format!("SELECT COUNT(*) FROM {table}{condition}");
+        debug_sql(&sql);
+        let total: i64 = connection
+            .prepare_cached(&sql)?
+            .query_row(params.as_slice(), |r| r.get(0))?;
+
+        let sql = format!(
+            "SELECT * FROM {table}{condition} ORDER BY rowid LIMIT {page_size} OFFSET {}",
+            page.saturating_mul(page_size)
+        );
+        debug_sql(&sql);
+        let mut s = connection.prepare_cached(&sql)?;
+        let mut rows = s.query(params.as_slice())?;
+        let mut items = Vec::new();
+        while let Some(row) = rows.next()? {
+            items.push(T::try_from_row(row, connection)?);
+        }
+        Ok(Page {
+            items,
+            total: total as usize,
+            page,
+            page_size,
+        })
+    })();
+    execute(connection, "RELEASE silo_paginate", ())?;
+    result
+}
+
+/// Loads all rows whose key is one of `keys` and returns them keyed by it.
+/// Keys without a row are missing from the result. The keys are loaded with
+/// an IN filter, which is split at the variable limit of the connection.
+pub fn get_many<'a, T: ToTable<'a>, F: filter::Filter, K: filter::InFilterable + Hash + Eq>(
+    connection: &&'a rusqlite::Connection,
+    table: TableName<'_>,
+    keys: Vec<K>,
+    filter: impl FnOnce(K::Filter) -> F,
+    key_of: impl Fn(&T) -> K,
+) -> Result<HashMap<K, T>, Error> {
+    if keys.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let filter = filter(K::convert_to_in_filter(keys));
+    Ok(load_where::<T, F>(connection, table, filter)?
+        .into_iter()
+        .map(|row| (key_of(&row), row))
+        .collect())
+}
+
+/// Loads the rows matching the filter, lets `f` change them and writes the
+/// rows, for which it returns true, back by their rowid. The rows are written
+/// inside of a savepoint, so either all changes are stored or none. Columns of
+/// `#[silo(skip_select)]` fields are not written, since they were never read.
+/// Returns how many rows were written.
+pub fn update_each<'a, T: ToTable<'a>, F: filter::Filter>(
+    connection: &&'a rusqlite::Connection,
+    table: TableName<'_>,
+    filter: impl Into<F>,
+    mut f: impl FnMut(&mut T) -> bool,
+) -> Result<usize, Error> {
+    let rows = load_where_with_rowid::<T, F>(connection, table, filter)?;
+    let columns = T::columns(None, false, false);
+    let assignments = columns
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| !c.is_skip_select)
+        .map(|(i, c)| format!("\"{}\" = ?{}", c.name, i + 1))
+        .collect::<Vec<_>>();
+    if assignments.is_empty() {
+        return Ok(0);
+    }
+    let sql = format!(
+        "UPDATE {table} SET {} WHERE rowid = ?{}",
+        assignments.join(", "),
+        columns.len() + 1
+    );
+
+    execute(connection, "SAVEPOINT silo_update_each", ())?;
+    let result = rows.into_iter().try_fold(0, |updated, (rowid, mut row)| {
+        if !f(&mut row) {
+            return Ok(updated);
+        }
+        debug_sql(&sql);
+        let mut statement = connection.prepare_cached(&sql)?;
+        let params = row.as_params();
+        let params: Vec<_> = params
+            .iter()
+            .map(|p| p.as_dyn())
+            .chain([&rowid as &dyn rusqlite::ToSql])
+            .collect();
+        Ok(updated + statement.execute(params.as_slice())?)
+    });
+    if result.is_err() {
+        execute(connection, "ROLLBACK TO silo_update_each", ())?;
+    }
+    execute(connection, "RELEASE silo_update_each", ())?;
+    result
+}
+
+/// Like [`load_where`], but hands every row to `callback` as soon as it is
+/// read, instead of collecting all rows first.
+pub fn for_each_where<'a, T: ToTable<'a>, F: filter::Filter>(
+    connection: &&'a rusqlite::Connection,
+    table: TableName<'_>,
+    filter: impl Into<F>,
+    mut callback: impl FnMut(T),
+) -> Result<(), Error> {
+    for_each_selected::<F>(connection, table, "*", filter.into(), |r| {
+        callback(T::try_from_row(r, connection)?);
+        Ok(())
+    })
+}
+
+/// Like [`load_where`], but pairs every row with its rowid.
+pub fn load_where_with_rowid<'a, T: ToTable<'a>, F: filter::Filter>(
+    connection: &&'a rusqlite::Connection,
+    table: TableName<'_>,
+    filter: impl Into<F>,
+) -> Result<Vec<(i64, T)>, Error> {
+    select_where::<F, (i64, T)>(
+        connection,
+        table,
+        "rowid AS __silo_rowid, *",
+        filter.into(),
+        |r| {
+            let rowid = r.get("__silo_rowid")?;
+            Ok((rowid, T::try_from_row(r, connection)?))
+        },
+    )
+}
+
+pub fn exists<'a, T: ToTable<'a>, F: filter::Filter>(
+    connection: &rusqlite::Connection,
+    table: TableName<'_>,
+    filter: impl Into<F>,
+) -> Result<bool, Error> {
+    for filter in split_filter(connection, filter.into(), 0)? {
+        let mut sql = format!("SELECT EXISTS(SELECT 1 FROM {table} WHERE ");
+        filter.to_sql(&mut sql, None);
+        let mut sql = sql.trim_end_matches(" WHERE ").to_string();
+        sql.push(')');
+        debug_sql(&sql);
+
+        let params = filter.as_params();
+        let params: Vec<_> = params.iter().map(|p| p.as_dyn()).collect();
+        if connection.query_row(&sql, params.as_slice(), |r| r.get(0))? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Returns `Error::MissingColumn` if the table has no column with this name.
+fn ensure_column<'a, T: ToTable<'a>>(column: &str) -> Result<(), Error> {
+    if T::columns(None, false, false)
+        .iter()
+        .any(|c| c.name == column)
+    {
+        Ok(())
+    } else {
+        Err(Error::MissingColumn(column.to_string().into()))
+    }
+}
+
+pub fn aggregate<'a, T: ToTable<'a>, F: filter::Filter>(
+    connection: &rusqlite::Connection,
+    table: TableName<'_>,
+    column: &str,
+    aggregate: Aggregate,
+    filter: impl Into<F>,
+) -> Result<Option<f64>, Error> {
+    let Some(r#type) = T::columns(None, false, false)
+        .into_iter()
+        .find(|c| c.name == column)
+        .map(|c| c.r#type)
+    else {
+        return Err(Error::MissingColumn(column.to_string().into()));
+    };
+    if !r#type.is_numeric() {
+        let actual = match r#type {
+            SqlColumnType::Text | SqlColumnType::OptionalText => rusqlite::types::Type::Text,
+            SqlColumnType::Blob | SqlColumnType::OptionalBlob => rusqlite::types::Type::Blob,
+            _ => rusqlite::types::Type::Null,
+        };
+        return Err(Error::WrongColumnType("a number".into(), actual));
+    }
+
+    // Every part of a split filter is aggregated on its own, so the parts are
+    // combined from their total, count, minimum and maximum.
+    let (mut total, mut count, mut min, mut max) = (0.0, 0, None::<f64>, None::<f64>);
+    for filter in split_filter(connection, filter.into(), 0)? {
+        let mut sql = format!(
+            "SELECT TOTAL(\"{column}\"), COUNT(\"{column}\"), MIN(\"{column}\"), MAX(\"{column}\") FROM {table} WHERE "
+        );
+        filter.to_sql(&mut sql, None);
+        let sql = sql.trim_end_matches(" WHERE ");
+        debug_sql(sql);
+        let mut statement = connection.prepare_cached(sql)?;
+        let params = filter.as_params();
+        let params: Vec<_> = params.iter().map(|p| p.as_dyn()).collect();
+        let (part_total, part_count, part_min, part_max) =
+            statement.query_row(params.as_slice(), |r| {
+                Ok((
+                    r.get::<_, f64>(0)?,
+                    r.get::<_, i64>(1)?,
+                    r.get::<_, Option<f64>>(2)?,
+                    r.get::<_, Option<f64>>(3)?,
+                ))
+            })?;
+        total += part_total;
+        count += part_count;
+        min = min.into_iter().chain(part_min).reduce(f64::min);
+        max = max.into_iter().chain(part_max).reduce(f64::max);
+    }
+    if count == 0 {
+        return Ok(None);
+    }
+    Ok(match aggregate {
+        Aggregate::Sum => Some(total),
+        Aggregate::Min => min,
+        Aggregate::Max => max,
+        Aggregate::Avg => Some(total / count as f64),
+    })
+}
+
+pub fn count_distinct<'a, T: ToTable<'a>>(
+    connection: &rusqlite::Connection,
+    table: TableName<'_>,
+    column: &str,
+) -> Result<usize, Error> {
+    ensure_column::<T>(column)?;
+    let mut sql = format!("SELECT COUNT(DISTINCT \"{column}\") FROM {table}");
+    if T::IS_SOFT_DELETE {
+        _ = write!(sql, " WHERE \"{SOFT_DELETE_COLUMN}\" IS NULL");
+    }
+    debug_sql(&sql);
+    Ok(connection.query_row(&sql, (), |r| r.get(0))?)
+}
+
+pub fn update_expr<'a, T: ToTable<'a>, F: filter::Filter>(
+    connection: &rusqlite::Connection,
+    table: TableName<'_>,
+    filter: impl Into<F>,
+    column: &str,
+    value: &expr::Expr,
+) -> Result<usize, Error> {
+    ensure_column::<T>(column)?;
+    for referenced in value.columns() {
+        ensure_column::<T>(referenced)?;
+    }
+    let mut set = format!("UPDATE {table} SET \"{column}\" = ");
+    let mut value_params = Vec::new();
+    value.to_sql(&mut set, &mut value_params);
+
+    let mut updated = 0;
+    for filter in split_filter(connection, filter.into(), value_params.len())? {
+        let mut sql = set.clone();
+        sql.push_str(" WHERE ");
+        filter.to_sql(&mut sql, None);
+        let sql = sql.trim_end_matches(" WHERE ");
+        debug_sql(sql);
+
+        let mut statement = connection.prepare_cached(sql)?;
+        let filter_params = filter.as_params();
+        let params: Vec<_> = value_params
+            .iter()
+            .map(|p| *p as &dyn rusqlite::ToSql)
+            .chain(filter_params.iter().map(|p| p.as_dyn()))
+            .collect();
+        updated += statement.execute(params.as_slice())?;
+    }
+    Ok(updated)
 }
 
 pub fn update<'a, T: ToTable<'a>, V: AsParamsOptional + AsColumnsOptional, F: filter::Filter>(
     connection: &&'a rusqlite::Connection,
+    table: TableName<'_>,
     filter: impl Into<F>,
     value: V,
-) -> Result<usize, rusqlite::Error> {
+) -> Result<usize, Error> {
     let filter = filter.into();
     let columns = value
         .columns_skip_optional(None, false, false)
@@ -834,14 +3087,25 @@ pub fn update<'a, T: ToTable<'a>, V: AsParamsOptional + AsColumnsOptional, F: fi
                 acc
             }
         });
-    let mut sql = format!("UPDATE \"{}\" SET {columns}", T::NAME);
-    sql.push_str(" WHERE ");
-    filter.to_sql(&mut sql, None);
-    let sql = sql.trim_end_matches(" WHERE ");
-    debug_sql(sql);
-
-    let mut statement = connection.prepare(sql)?;
-    let params = value.as_params_skip_optional();
-    let params: Vec<_> = params.iter().map(|p| p.as_dyn()).collect();
-    statement.execute(params.as_slice())
+    let value_params = value.as_params_skip_optional();
+    let mut updated = 0;
+    for filter in split_filter(connection, filter, value_params.len())? {
+        let mut sql = format!("UPDATE {table} SET {columns}");
+        sql.push_str(" WHERE ");
+        filter.to_sql(&mut sql, None);
+        let sql = sql.trim_end_matches(" WHERE ");
+        debug_sql(sql);
+
+        let mut statement = connection.prepare_cached(sql)?;
+        // The anonymous parameters of the filter are numbered after the
+        // numbered parameters of the updated columns.
+        let filter_params = filter.as_params();
+        let params: Vec<_> = value_params
+            .iter()
+            .chain(&filter_params)
+            .map(|p| p.as_dyn())
+            .collect();
+        updated += statement.execute(params.as_slice())?;
+    }
+    Ok(updated)
 }
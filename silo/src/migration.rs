@@ -0,0 +1,62 @@
+use crate::{Error, debug_sql, execute};
+
+/// The table, which remembers the versions of the migrations already run.
+pub const MIGRATIONS_TABLE: &str = "_silo_migrations";
+
+/// A step changing the database, which cannot be inferred from the columns of
+/// a table, e.g. moving data between tables. See [`crate::Database::run_migrations`].
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    /// Migrations run in ascending order of their version. A version is only
+    /// ever run once per database, so it must not be reused for a different
+    /// step.
+    pub version: u32,
+    pub up: fn(&rusqlite::Connection) -> Result<(), Error>,
+}
+
+/// Runs every migration, whose version is not yet recorded, in ascending
+/// order. Each migration is run together with recording its version inside of
+/// a savepoint, so a failed migration leaves no trace and is run again next
+/// time. Returns how many migrations were run.
+pub(crate) fn run(
+    connection: &rusqlite::Connection,
+    migrations: &[Migration],
+) -> Result<usize, Error> {
+    execute(
+        connection,
+        &format!(
+            "CREATE TABLE IF NOT EXISTS \"{MIGRATIONS_TABLE}\" (\"version\" INTEGER PRIMARY KEY NOT NULL)"
+        ),
+        (),
+    )?;
+    let mut migrations = migrations.to_vec();
+    migrations.sort_by_key(|m| m.version);
+    let mut count = 0;
+    for migration in migrations {
+        if is_applied(connection, migration.version)? {
+            continue;
+        }
+        execute(connection, "SAVEPOINT silo_migration", ())?;
+        let result = (migration.up)(connection).and_then(|()| {
+            execute(
+                connection,
+                &format!("INSERT INTO \"{MIGRATIONS_TABLE}\" (\"version\") VALUES (?)"),
+                [migration.version],
+            )?;
+            Ok(())
+        });
+        if result.is_err() {
+            execute(connection, "ROLLBACK TO silo_migration", ())?;
+        }
+        execute(connection, "RELEASE silo_migration", ())?;
+        result?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+fn is_applied(connection: &rusqlite::Connection, version: u32) -> Result<bool, Error> {
+    let sql = format!("SELECT EXISTS(SELECT 1 FROM \"{MIGRATIONS_TABLE}\" WHERE \"version\" = ?)");
+    debug_sql(&sql);
+    Ok(connection.query_row(&sql, [version], |r| r.get(0))?)
+}
@@ -0,0 +1,131 @@
+use std::fmt::Write;
+
+use rusqlite::types::{ToSqlOutput, Value, ValueRef};
+
+use crate::{
+    AsParams, Error, ExtractFromRow, IsSingleColumn, SqlColumnType, ToSqlDyn,
+    filter::{
+        ComparisonOperator, FieldFilter, Filterable, InFilterable, IsFieldFilter, WriteToSql,
+    },
+    partial,
+};
+
+/// A float, which keeps NaN apart from NULL. SQLite stores a plain NaN as
+/// NULL, so a NaN `f64` reads back as an error and a NaN `Option<f64>` reads
+/// back as `None`. This type stores NaN as the text `'NaN'` instead, so
+/// `Option<KeepNan<f64>>` reads `Some(KeepNan(NaN))` and `None` as they were
+/// stored.
+///
+/// Since the column then holds text, it cannot be part of a STRICT table.
+/// Comparisons never match NaN, only equality with NaN does.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+pub struct KeepNan<T>(pub T);
+
+const NAN: &str = "NaN";
+
+macro_rules! impl_keep_nan {
+    ($t:ty) => {
+        impl From<$t> for KeepNan<$t> {
+            fn from(value: $t) -> Self {
+                Self(value)
+            }
+        }
+
+        impl rusqlite::ToSql for KeepNan<$t> {
+            fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+                if self.0.is_nan() {
+                    Ok(ToSqlOutput::Borrowed(ValueRef::Text(NAN.as_bytes())))
+                } else {
+                    Ok(ToSqlOutput::Owned(Value::Real(self.0 as f64)))
+                }
+            }
+        }
+
+        impl IsSingleColumn for KeepNan<$t> {
+            const SQL_COLUMN_TYPE: SqlColumnType = SqlColumnType::Float;
+        }
+
+        impl AsParams for KeepNan<$t> {
+            fn as_params<'b>(&'b self) -> Vec<ToSqlDyn<'b>> {
+                vec![ToSqlDyn::Borrowed(self)]
+            }
+        }
+
+        impl ExtractFromRow for KeepNan<$t> {
+            fn try_from_row_simple(column_name: &str, row: &rusqlite::Row) -> Result<Self, Error> {
+                match row.get_ref(column_name) {
+                    Ok(ValueRef::Real(it)) => Ok(Self(it as $t)),
+                    Ok(ValueRef::Integer(it)) => Ok(Self(it as $t)),
+                    Ok(ValueRef::Text(it)) if it == NAN.as_bytes() => Ok(Self(<$t>::NAN)),
+                    Ok(ValueRef::Text(it)) => Err(Error::IllFormattedColumn(
+                        "KeepNan".into(),
+                        String::from_utf8_lossy(it).into_owned(),
+                        None,
+                    )),
+                    Ok(it) => Err(Error::WrongColumnType("KeepNan".into(), it.data_type())),
+                    Err(rusqlite::Error::InvalidColumnName(_)) => {
+                        Err(Error::MissingColumn(column_name.to_string().into()))
+                    }
+                    Err(err) => Err(err.into()),
+                }
+            }
+        }
+
+        impl partial::HasPartial for KeepNan<$t> {
+            type Partial = Option<KeepNan<$t>>;
+        }
+
+        impl WriteToSql for KeepNan<$t> {
+            fn write_to_sql(&self, sql: &mut String, operator: ComparisonOperator) {
+                if self.0.is_nan() {
+                    _ = write!(sql, "'{NAN}'");
+                } else {
+                    self.0.write_to_sql(sql, operator);
+                }
+            }
+        }
+
+        impl IsFieldFilter for KeepNan<$t> {
+            fn to_sql(&self, sql: &mut String, operator: ComparisonOperator, parent: &str) {
+                if self.0.is_nan() && operator != ComparisonOperator::Equals {
+                    // Like every comparison with NaN, this never matches.
+                    _ = write!(sql, "{parent} {operator} NULL");
+                    return;
+                }
+                // Text sorts after every number, so NaN is excluded from
+                // comparisons explicitly.
+                _ = write!(sql, "({parent} {operator} ");
+                self.write_to_sql(sql, operator);
+                if self.0.is_nan() {
+                    sql.push(')');
+                } else {
+                    _ = write!(sql, " AND {parent} IS NOT '{NAN}')");
+                }
+            }
+        }
+
+        impl Filterable for KeepNan<$t> {
+            type Filter = FieldFilter<KeepNan<$t>>;
+
+            fn convert_to_equals_filter(self) -> Self::Filter {
+                FieldFilter::equals(self)
+            }
+        }
+
+        impl InFilterable for KeepNan<$t> {
+            fn convert_to_in_filter(values: Vec<Self>) -> Self::Filter {
+                FieldFilter::In(values)
+            }
+        }
+
+        impl FieldFilter<KeepNan<$t>> {
+            /// Matches rows where the value is NaN.
+            pub fn is_nan() -> Self {
+                Self::equals(KeepNan(<$t>::NAN))
+            }
+        }
+    };
+}
+
+impl_keep_nan!(f32);
+impl_keep_nan!(f64);
@@ -14,6 +14,9 @@ pub trait HasPartial<T = Self>: Sized + Into<Self::Partial> {
     // type Partial: PartialType<T>;
 }
 
+/// The partial of an optional field has three states for updates: `None`
+/// keeps the column, `Some(None)` sets it to NULL and `Some(Some(value))` sets
+/// it to the value.
 impl<T: HasPartial> HasPartial for Option<T> {
     type Partial = Option<Option<T>>;
 }
@@ -1,8 +1,11 @@
-use std::{any::type_name, borrow::Cow, marker::PhantomData, ops::RangeBounds};
+use std::{
+    any::type_name, borrow::Cow, collections::HashSet, fmt::Write, marker::PhantomData,
+    ops::RangeBounds,
+};
 
-use rusqlite::Connection;
+use rusqlite::{Connection, types::ValueRef};
 
-use crate::{Error, ToTable, debug_sql, filter::Filter};
+use crate::{Error, TableName, ToTable, debug_sql, filter::Filter, split_filter};
 
 pub struct ProjectionColumns(Vec<Cow<'static, str>>);
 
@@ -62,7 +65,7 @@ macro_rules! impl_projectable_single_column {
                     Err(rusqlite::Error::InvalidColumnType(.., t)) => {
                         Err(Error::WrongColumnType(stringify!($t).into(), t))
                     }
-                    Err(err) => unreachable!("Impossible error? {err}"),
+                    Err(err) => Err(err.into()),
                 }
             }
         }
@@ -70,7 +73,18 @@ macro_rules! impl_projectable_single_column {
 }
 
 impl_projectable_single_column!(String);
+impl_projectable_single_column!(bool);
 impl_projectable_single_column!(u8);
+impl_projectable_single_column!(u16);
+impl_projectable_single_column!(u32);
+impl_projectable_single_column!(usize);
+impl_projectable_single_column!(i8);
+impl_projectable_single_column!(i16);
+impl_projectable_single_column!(i32);
+impl_projectable_single_column!(i64);
+impl_projectable_single_column!(isize);
+impl_projectable_single_column!(f32);
+impl_projectable_single_column!(f64);
 
 macro_rules! impl_projectable_tuples {
     ($($t:ident),+$(,)?) => {
@@ -130,6 +144,7 @@ where
 {
     columns: ProjectionColumns,
     unique_only: bool,
+    limit: Option<usize>,
     output: PhantomData<P>,
 }
 impl<P: Projectable> Projection<P> {
@@ -151,6 +166,7 @@ impl<P: Projectable> Projection<P> {
         Self {
             columns,
             unique_only: false,
+            limit: None,
             output: PhantomData,
         }
     }
@@ -159,13 +175,20 @@ impl<P: Projectable> Projection<P> {
         self.unique_only = distinct;
         self
     }
+
+    /// Reads at most `limit` rows.
+    pub(crate) fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
 }
 
 pub fn project<'a, T: ToTable<'a>, P: Projectable, F: Filter>(
     connection: &Connection,
+    table: TableName<'_>,
     projection: Projection<P>,
     filter: impl Into<F>,
-) -> rusqlite::Result<Vec<P>> {
+) -> Result<Vec<P>, Error> {
     if projection.columns.0.len() != P::COUNT {
         panic!(
             "Mismatch between wanted columns ({}) in return type and given column names ({}). In nightly, you can enable compile time checks for this.\n\nExpected type was: {}\nGiven column names were:\n  {}",
@@ -175,14 +198,51 @@ pub fn project<'a, T: ToTable<'a>, P: Projectable, F: Filter>(
             projection.columns.0.join("\n  ")
         );
     }
-    let filter = filter.into();
     let columns = projection.columns_to_sql();
-    let mut sql = format!("SELECT {columns} FROM {} WHERE ", T::NAME);
-    filter.to_sql(&mut sql, None);
-    let sql = sql.trim_end_matches(" WHERE ");
-    debug_sql(sql);
-    let mut s = connection.prepare(sql)?;
-    s.query(())?
-        .mapped(|r| P::from_row(&projection.columns, r, connection).map_err(|e| todo!("{}", e)))
+    let mut result = Vec::new();
+    let filters = split_filter(connection, filter.into(), 0)?;
+    // DISTINCT only applies to a single part of a split filter, so the rows
+    // of the parts are deduplicated by their values here.
+    let mut seen = (projection.unique_only && filters.len() > 1).then(HashSet::new);
+    for filter in filters {
+        let mut sql = format!("SELECT {columns} FROM {table} WHERE ");
+        filter.to_sql(&mut sql, None);
+        let mut sql = sql.trim_end_matches(" WHERE ").to_string();
+        if let Some(limit) = projection.limit {
+            if result.len() >= limit {
+                break;
+            }
+            _ = write!(sql, " LIMIT {}", limit - result.len());
+        }
+        debug_sql(&sql);
+        let mut s = connection.prepare_cached(&sql)?;
+        let params = filter.as_params();
+        let params: Vec<_> = params.iter().map(|p| p.as_dyn()).collect();
+        let mut rows = s.query(params.as_slice())?;
+        while let Some(row) = rows.next()? {
+            if let Some(seen) = &mut seen
+                && !seen.insert(row_values(row, projection.columns.0.len())?)
+            {
+                continue;
+            }
+            result.push(P::from_row(&projection.columns, row, connection)?);
+        }
+    }
+    Ok(result)
+}
+
+/// The values of the first `count` columns of the row in a form, which can be
+/// hashed. Two rows have the same values, if DISTINCT would merge them.
+fn row_values(row: &rusqlite::Row, count: usize) -> Result<Vec<(u8, Vec<u8>)>, Error> {
+    (0..count)
+        .map(|i| {
+            Ok(match row.get_ref(i)? {
+                ValueRef::Null => (0, Vec::new()),
+                ValueRef::Integer(it) => (1, it.to_le_bytes().to_vec()),
+                ValueRef::Real(it) => (2, it.to_bits().to_le_bytes().to_vec()),
+                ValueRef::Text(it) => (3, it.to_vec()),
+                ValueRef::Blob(it) => (4, it.to_vec()),
+            })
+        })
         .collect()
 }
@@ -0,0 +1,209 @@
+use std::borrow::Cow;
+
+use crate::{Quoted, SqlColumn, SqlColumnType, TableName, debug_sql};
+
+/// A difference between the columns silo expects for a table and the columns
+/// the table in the database actually has.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaMismatch {
+    MissingTable {
+        table: &'static str,
+    },
+    MissingColumn {
+        table: &'static str,
+        column: Cow<'static, str>,
+    },
+    UnexpectedColumn {
+        table: &'static str,
+        column: String,
+    },
+    WrongColumnType {
+        table: &'static str,
+        column: Cow<'static, str>,
+        expected: &'static str,
+        actual: String,
+    },
+}
+
+/// The expected columns of a table, which was registered with the database.
+#[derive(Debug, Clone)]
+pub(crate) struct RegisteredTable {
+    pub name: &'static str,
+    pub columns: Vec<SqlColumn>,
+}
+
+/// A column as reported by `PRAGMA table_info`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ActualColumn {
+    pub name: String,
+    pub r#type: String,
+}
+
+pub(crate) fn actual_columns(
+    connection: &rusqlite::Connection,
+    table: TableName<'_>,
+) -> Result<Vec<ActualColumn>, rusqlite::Error> {
+    let sql = format!(
+        "PRAGMA {}table_info({})",
+        table.schema_prefix(),
+        Quoted(table.name)
+    );
+    debug_sql(&sql);
+    let mut statement = connection.prepare(&sql)?;
+    statement
+        .query_map((), |r| {
+            Ok(ActualColumn {
+                name: r.get("name")?,
+                r#type: r.get("type")?,
+            })
+        })?
+        .collect()
+}
+
+/// Reads the columns of any table from `PRAGMA table_info`, `index_list` and
+/// `foreign_key_list`. Collations and AUTOINCREMENT are not reported, so
+/// `is_nocase` and `is_autoincrement` are always false. Declared types are
+/// mapped by their affinity, see `column_type`. Every pragma is run on the
+/// database of the table.
+pub(crate) fn introspect(
+    connection: &rusqlite::Connection,
+    table: TableName<'_>,
+) -> Result<Vec<SqlColumn>, rusqlite::Error> {
+    let prefix = table.schema_prefix();
+    let name = Quoted(table.name);
+    let sql = format!("PRAGMA {prefix}table_info({name})");
+    debug_sql(&sql);
+    let mut columns = connection
+        .prepare(&sql)?
+        .query_map((), |r| {
+            let r#type: String = r.get("type")?;
+            Ok(SqlColumn {
+                name: r.get::<_, String>("name")?.into(),
+                r#type: column_type(&r#type),
+                is_primary: r.get::<_, i64>("pk")? > 0,
+                is_unique: false,
+                is_indexed: false,
+                is_unique_indexed: false,
+                is_nocase: false,
+                default: r.get::<_, Option<String>>("dflt_value")?.map(Into::into),
+                references: None,
+                is_skip_insert: false,
+                is_autoincrement: false,
+                is_skip_select: false,
+                check: None,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let sql = format!("PRAGMA {prefix}index_list({name})");
+    debug_sql(&sql);
+    let indices = connection
+        .prepare(&sql)?
+        .query_map((), |r| {
+            Ok((
+                r.get::<_, String>("name")?,
+                r.get::<_, bool>("unique")?,
+                r.get::<_, String>("origin")?,
+                r.get::<_, bool>("partial")?,
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    for (index, is_unique, origin, is_partial) in indices {
+        let sql = format!("PRAGMA {prefix}index_info({})", Quoted(&index));
+        debug_sql(&sql);
+        let indexed: Vec<String> = connection
+            .prepare(&sql)?
+            .query_map((), |r| r.get("name"))?
+            .collect::<Result<_, _>>()?;
+        // Only indices of a single column are represented by a flag.
+        let [indexed] = indexed.as_slice() else {
+            continue;
+        };
+        let Some(column) = columns.iter_mut().find(|c| &c.name == indexed) else {
+            continue;
+        };
+        match (origin.as_str(), is_unique) {
+            ("u", _) => column.is_unique = true,
+            ("c", true) if is_partial => column.is_unique_indexed = true,
+            ("c", false) => column.is_indexed = true,
+            _ => {}
+        }
+    }
+
+    let sql = format!("PRAGMA {prefix}foreign_key_list({name})");
+    debug_sql(&sql);
+    let references = connection
+        .prepare(&sql)?
+        .query_map((), |r| {
+            Ok((r.get::<_, String>("from")?, r.get::<_, String>("table")?))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    for (from, table) in references {
+        if let Some(column) = columns.iter_mut().find(|c| c.name == from) {
+            column.references = Some(table.into());
+        }
+    }
+    Ok(columns)
+}
+
+/// Maps a declared type to a column type by the affinity rules of SQLite, see
+/// <https://sqlite.org/datatype3.html#determination_of_column_affinity>. So
+/// `VARCHAR(255)` is [`SqlColumnType::Text`] and `BIGINT` is
+/// [`SqlColumnType::Integer`]. Types without a type or with NUMERIC affinity
+/// are reported as [`SqlColumnType::Null`].
+fn column_type(r#type: &str) -> SqlColumnType {
+    let r#type = r#type.to_ascii_uppercase();
+    if r#type.contains("INT") {
+        SqlColumnType::Integer
+    } else if ["CHAR", "CLOB", "TEXT"].iter().any(|t| r#type.contains(t)) {
+        SqlColumnType::Text
+    } else if r#type.contains("BLOB") {
+        SqlColumnType::Blob
+    } else if ["REAL", "FLOA", "DOUB"].iter().any(|t| r#type.contains(t)) {
+        SqlColumnType::Float
+    } else {
+        SqlColumnType::Null
+    }
+}
+
+/// Whether a column declared as `actual` stores values of the `expected`
+/// type. Columns with NUMERIC affinity store both integers and floats.
+fn has_affinity(expected: SqlColumnType, actual: &str) -> bool {
+    match column_type(actual) {
+        SqlColumnType::Null if !actual.trim().is_empty() && expected.is_numeric() => true,
+        actual => actual.as_sql() == expected.as_sql(),
+    }
+}
+
+pub(crate) fn compare_columns(
+    table: &RegisteredTable,
+    actual: &[ActualColumn],
+) -> Vec<SchemaMismatch> {
+    let mut result = Vec::new();
+    for expected in &table.columns {
+        match actual.iter().find(|a| a.name == expected.name) {
+            Some(actual) if !has_affinity(expected.r#type, &actual.r#type) => {
+                result.push(SchemaMismatch::WrongColumnType {
+                    table: table.name,
+                    column: expected.name.clone(),
+                    expected: expected.r#type.as_sql(),
+                    actual: actual.r#type.clone(),
+                });
+            }
+            Some(_) => {}
+            None => result.push(SchemaMismatch::MissingColumn {
+                table: table.name,
+                column: expected.name.clone(),
+            }),
+        }
+    }
+    for actual in actual {
+        if !table.columns.iter().any(|c| c.name == actual.name) {
+            result.push(SchemaMismatch::UnexpectedColumn {
+                table: table.name,
+                column: actual.name.clone(),
+            });
+        }
+    }
+    result
+}
@@ -1,17 +1,26 @@
 use silo::derive::{ToColumns, ToTable};
+#[cfg(feature = "uuid")]
 use uuid::Uuid;
 
 use crate::{
-    self as silo, AsColumns, AsColumnsDynamicallySized, Database, SqlTable, column_name_of,
-    filter::{FieldFilter, Filterable, OptionalFilter},
+    self as silo, Aggregate, AsColumns, AsColumnsDynamicallySized, CheckpointMode, Database, Error,
+    GenericOrder, HasPrimaryKey, Int128, Ordering, OrderingAscDesc, OrderingNulls, Pragmas,
+    SOFT_DELETE_COLUMN, SaveOptions, SqlFailureBehavior, SqlTable, TableName, column_name_of,
+    expr::Expr,
+    filter::{FieldFilter, Filterable, OptionalFilter, ResultFilter},
+    migration::Migration,
+    nan::KeepNan,
+    schema::SchemaMismatch,
 };
 
+#[cfg(feature = "uuid")]
 #[derive(Default, Debug, PartialEq, Eq, Clone, ToColumns)]
 struct AddressTC {
     city: String,
     street: String,
 }
 
+#[cfg(feature = "uuid")]
 #[derive(Default, PartialEq, Eq, Debug, Clone, ToTable)]
 struct Person {
     name: String,
@@ -22,6 +31,7 @@ struct Person {
     residence: AddressTC,
 }
 
+#[cfg(feature = "uuid")]
 #[test]
 fn test_person_filter() {
     let db = Database::create_in_memory().unwrap();
@@ -193,15 +203,17 @@ fn test_person_filter() {
     let loaded = persons
         .load_where(PersonFilter {
             name: alice.name.clone().convert_to_equals_filter(),
-            age: alice.age.clone().convert_to_equals_filter(),
+            age: alice.age.convert_to_equals_filter(),
             traditional_name: alice.traditional_name.clone().convert_to_equals_filter(),
-            id: alice.id.clone().convert_to_equals_filter(),
+            id: alice.id.convert_to_equals_filter(),
             residence: alice.residence.clone().convert_to_equals_filter(),
+            ..Default::default()
         })
         .unwrap();
     assert_eq!(loaded, [alice])
 }
 
+#[cfg(feature = "uuid")]
 #[test]
 fn update_person() {
     let db = Database::create_in_memory().unwrap();
@@ -287,6 +299,7 @@ fn update_person() {
         }
     );
 }
+#[cfg(feature = "uuid")]
 #[test]
 fn creates_table_for_nested_struct() {
     let db = Database::create_in_memory().unwrap();
@@ -315,6 +328,7 @@ fn creates_table_for_nested_struct() {
     assert!(sql.contains("\"residence_street\" TEXT"));
 }
 
+#[cfg(feature = "uuid")]
 #[test]
 fn insert_and_load_person() {
     let db = Database::create_in_memory().unwrap();
@@ -346,6 +360,7 @@ fn insert_and_load_person() {
     assert_eq!(loaded.residence.street, person.residence.street);
 }
 
+#[cfg(feature = "uuid")]
 #[test]
 fn nested_columns_are_flattened() {
     use silo::AsColumnsDynamicallySized;
@@ -473,6 +488,7 @@ fn test_rust_keywords_as_table_name_to_table() {
 
 #[test]
 fn test_rust_keywords_to_columns() {
+    #[allow(dead_code)]
     #[derive(Debug, Clone, ToColumns)]
     struct Foo {
         r#type: String,
@@ -517,6 +533,7 @@ fn test_sqlite_keywords_as_table_names_to_table() {
 
 #[test]
 fn test_sqlite_keywords_to_columns() {
+    #[allow(dead_code)]
     #[derive(Debug, Clone, ToColumns)]
     struct Foo {
         values: String,
@@ -544,12 +561,14 @@ fn all_builtin_types_are_supported() {
     assert_supported::<f64>();
 
     assert_supported::<String>();
+    #[cfg(feature = "uuid")]
     assert_supported::<Uuid>();
 
     assert_supported::<Option<i32>>();
     assert_supported::<Option<String>>();
 }
 
+#[cfg(feature = "uuid")]
 #[test]
 fn roundtrip_serialization() {
     #[derive(Debug, Clone, PartialEq, silo::derive::ToColumns)]
@@ -709,3 +728,4538 @@ fn test_skip_attribute() {
     assert_eq!(loaded.name, "Entry name");
     assert_eq!(loaded.id, None);
 }
+
+#[test]
+fn test_result_columns() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToColumns)]
+    struct Output {
+        value: u32,
+        message: String,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Operation {
+        #[silo(primary)]
+        id: u32,
+        outcome: Result<Output, String>,
+    }
+
+    let column_names: Vec<_> = Operation::columns(None, false, false)
+        .into_iter()
+        .map(|c| c.name)
+        .collect();
+    assert_eq!(
+        column_names,
+        [
+            "id",
+            "outcome",
+            "outcome_ok_value",
+            "outcome_ok_message",
+            "outcome_err"
+        ]
+    );
+
+    let success = Operation {
+        id: 1,
+        outcome: Ok(Output {
+            value: 42,
+            message: "done".into(),
+        }),
+    };
+    let failure = Operation {
+        id: 2,
+        outcome: Err("disk full".into()),
+    };
+
+    let db = Database::create_in_memory().unwrap();
+    let operations = db.load::<Operation>().unwrap();
+    operations.insert(success.clone()).unwrap();
+    operations.insert(failure.clone()).unwrap();
+
+    let loaded = operations.load_where(()).unwrap();
+    assert_eq!(loaded, vec![success.clone(), failure.clone()]);
+
+    let loaded = operations
+        .load_where(OperationFilter {
+            outcome: ResultFilter::IsOk,
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(loaded, vec![success.clone()]);
+
+    let loaded = operations
+        .load_where(OperationFilter {
+            outcome: ResultFilter::IsErrAnd(FieldFilter::equals("disk full")),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(loaded, vec![failure.clone()]);
+
+    let loaded = operations
+        .load_where(OperationFilter {
+            outcome: ResultFilter::IsErrAnd(FieldFilter::equals("done")),
+            ..Default::default()
+        })
+        .unwrap();
+    assert!(loaded.is_empty());
+}
+
+#[test]
+fn test_in_filter_is_split_at_variable_limit() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Item {
+        #[silo(primary)]
+        id: u32,
+        in_stock: bool,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    db.set_variable_limit(999).unwrap();
+    assert_eq!(db.variable_limit().unwrap(), 999);
+
+    let items = db.load::<Item>().unwrap();
+    for id in 0..6000 {
+        items
+            .insert(Item {
+                id,
+                in_stock: false,
+            })
+            .unwrap();
+    }
+
+    let keys: Vec<u32> = (0..5000).collect();
+    let loaded = items
+        .load_where(ItemFilter {
+            id: FieldFilter::is_one_of(keys.clone()),
+            ..Default::default()
+        })
+        .unwrap();
+    let mut loaded_keys: Vec<_> = loaded.into_iter().map(|i| i.id).collect();
+    loaded_keys.sort();
+    assert_eq!(loaded_keys, keys);
+
+    let updated = items
+        .update(
+            ItemFilter {
+                id: FieldFilter::is_one_of(keys.clone()),
+                ..Default::default()
+            },
+            PartialItem {
+                in_stock: Some(true),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(updated, 5000);
+
+    let in_stock = items
+        .load_where(ItemFilter {
+            in_stock: FieldFilter::equals(true),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(in_stock.len(), 5000);
+}
+
+#[test]
+fn test_upsert() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Genre {
+        #[silo(primary)]
+        id: u32,
+        name: String,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let genres = db.load::<Genre>().unwrap();
+    genres
+        .insert(Genre {
+            id: 1,
+            name: "Jazz".into(),
+        })
+        .unwrap();
+
+    let updated = Genre {
+        id: 1,
+        name: "Free Jazz".into(),
+    };
+    assert!(genres.upsert(updated.clone(), &[]).unwrap());
+    assert_eq!(genres.load_where(()).unwrap(), vec![updated]);
+
+    let inserted = Genre {
+        id: 2,
+        name: "Blues".into(),
+    };
+    assert!(genres.upsert(inserted.clone(), &["id"]).unwrap());
+    assert_eq!(genres.load_where(()).unwrap().len(), 2);
+    assert_eq!(genres.load_where(2).unwrap(), vec![inserted.clone()]);
+    assert!(matches!(
+        genres.upsert(inserted, &["title"]),
+        Err(Error::MissingColumn(c)) if c == "title"
+    ));
+
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Tag {
+        name: String,
+    }
+    let tags = db.load::<Tag>().unwrap();
+    assert!(matches!(
+        tags.upsert(Tag { name: "new".into() }, &[]),
+        Err(Error::MissingConflictColumns(_))
+    ));
+}
+
+#[test]
+fn test_get_field() {
+    #[derive(Debug, Clone, PartialEq, ToTable)]
+    struct Movie {
+        #[silo(primary)]
+        id: u32,
+        title: String,
+        rating: f32,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let movies = db.load::<Movie>().unwrap();
+    movies
+        .insert(Movie {
+            id: 7,
+            title: "Metropolis".into(),
+            rating: 8.3,
+        })
+        .unwrap();
+
+    let title: Option<String> = movies.get_field(7, column_name_of!(Movie, title)).unwrap();
+    assert_eq!(title.as_deref(), Some("Metropolis"));
+
+    let rating: Option<f32> = movies.get_field(7, "rating").unwrap();
+    assert_eq!(rating, Some(8.3));
+
+    let missing: Option<String> = movies.get_field(8, "title").unwrap();
+    assert_eq!(missing, None);
+
+    assert!(matches!(
+        movies.get_field::<String>(7, "director"),
+        Err(silo::Error::MissingColumn(_))
+    ));
+}
+
+#[test]
+fn test_increment() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Counter {
+        #[silo(primary)]
+        name: String,
+        views: i64,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let counters = db.load::<Counter>().unwrap();
+    counters
+        .insert(Counter {
+            name: "home".into(),
+            views: 0,
+        })
+        .unwrap();
+    counters
+        .insert(Counter {
+            name: "about".into(),
+            views: 10,
+        })
+        .unwrap();
+
+    for delta in 1..=10 {
+        let updated = counters
+            .increment("home".to_string(), column_name_of!(Counter, views), delta)
+            .unwrap();
+        assert_eq!(updated, 1);
+    }
+    counters
+        .increment("about".to_string(), "views", -3)
+        .unwrap();
+
+    assert_eq!(
+        counters
+            .get_field::<i64>("home".to_string(), "views")
+            .unwrap(),
+        Some(55)
+    );
+    assert_eq!(
+        counters
+            .get_field::<i64>("about".to_string(), "views")
+            .unwrap(),
+        Some(7)
+    );
+    assert!(matches!(
+        counters.increment("home".to_string(), "clicks", 1),
+        Err(silo::Error::MissingColumn(_))
+    ));
+}
+
+#[test]
+fn test_index_attribute() {
+    #[derive(Debug, Clone, ToTable)]
+    struct Track {
+        #[silo(primary)]
+        id: u32,
+        #[silo(index)]
+        artist: String,
+        title: String,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    db.load::<Track>().unwrap();
+    // Loading an existing table must not fail on the existing index.
+    db.load::<Track>().unwrap();
+
+    let mut statement = db
+        .connection
+        .prepare("PRAGMA index_list(\"Track\")")
+        .unwrap();
+    let indices: Vec<String> = statement
+        .query_map((), |r| r.get("name"))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert!(indices.contains(&"idx_Track_artist".to_string()));
+    assert!(!indices.iter().any(|i| i.contains("title")));
+}
+
+#[test]
+fn test_update_expr() {
+    #[derive(Debug, Clone, PartialEq, ToTable)]
+    struct Product {
+        #[silo(primary)]
+        id: u32,
+        price: f64,
+        discounted_price: f64,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let products = db.load::<Product>().unwrap();
+    for (id, price) in [(1, 10.0), (2, 25.0), (3, 7.5)] {
+        products
+            .insert(Product {
+                id,
+                price,
+                discounted_price: price,
+            })
+            .unwrap();
+    }
+
+    let updated = products
+        .update_expr((), "price", Expr::column("price") / Expr::literal(2.0))
+        .unwrap();
+    assert_eq!(updated, 3);
+    products
+        .update_expr(
+            ProductFilter {
+                id: FieldFilter::greater_than(1u32),
+                ..Default::default()
+            },
+            "discounted_price",
+            Expr::column("price") * Expr::literal(0.9),
+        )
+        .unwrap();
+
+    let prices: Vec<(u32, f64, f64)> = products
+        .project(
+            [
+                column_name_of!(Product, id),
+                column_name_of!(Product, price),
+                column_name_of!(Product, discounted_price),
+            ],
+            (),
+        )
+        .unwrap();
+    assert_eq!(
+        prices,
+        vec![(1, 5.0, 10.0), (2, 12.5, 11.25), (3, 3.75, 3.375)]
+    );
+
+    assert!(matches!(
+        products.update_expr((), "price", Expr::column("cost")),
+        Err(silo::Error::MissingColumn(_))
+    ));
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+fn test_exists() {
+    let db = Database::create_in_memory().unwrap();
+    let persons = db.load::<Person>().unwrap();
+    assert!(!persons.exists(()).unwrap());
+
+    persons
+        .insert(Person {
+            name: "Alice".into(),
+            age: 25,
+            id: Uuid::NAMESPACE_X500,
+            ..Default::default()
+        })
+        .unwrap();
+    assert!(persons.exists(()).unwrap());
+    assert!(persons.exists(Uuid::NAMESPACE_X500).unwrap());
+    assert!(
+        !persons
+            .exists(PersonFilter {
+                age: FieldFilter::greater_than(30),
+                ..Default::default()
+            })
+            .unwrap()
+    );
+}
+
+#[test]
+fn test_load_where_with_rowid() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct LogEntry {
+        message: String,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let log = db.load::<LogEntry>().unwrap();
+    for message in ["started", "running", "stopped"] {
+        log.insert(LogEntry {
+            message: message.into(),
+        })
+        .unwrap();
+    }
+
+    let loaded = log
+        .load_where_with_rowid(LogEntryFilter {
+            message: FieldFilter::not(FieldFilter::equals("running")),
+            ..Default::default()
+        })
+        .unwrap();
+
+    let mut statement = db
+        .connection
+        .prepare("SELECT rowid, message FROM LogEntry WHERE message != 'running'")
+        .unwrap();
+    let expected: Vec<(i64, LogEntry)> = statement
+        .query_map((), |r| Ok((r.get(0)?, LogEntry { message: r.get(1)? })))
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(loaded, expected);
+    assert_eq!(loaded.len(), 2);
+}
+
+#[test]
+fn test_for_each() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Coord {
+        x: i32,
+        y: i32,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let coords = db.load::<Coord>().unwrap();
+    for i in 0..100 {
+        coords.insert(Coord { x: i, y: -i }).unwrap();
+    }
+
+    let mut count = 0;
+    let mut sum = 0;
+    coords
+        .for_each(
+            CoordFilter {
+                x: FieldFilter::greater_than_equals(50),
+                ..Default::default()
+            },
+            |c| {
+                assert_eq!(c.x, -c.y);
+                count += 1;
+                sum += c.x;
+            },
+        )
+        .unwrap();
+    assert_eq!(count, 50);
+    assert_eq!(sum, (50..100).sum::<i32>());
+}
+
+#[test]
+fn test_save_with() {
+    #[derive(Debug, Clone, Default, ToTable)]
+    struct Note {
+        text: String,
+    }
+
+    let dir = std::env::temp_dir().join(format!("silo-test-save-with-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("backup.db");
+
+    let db = Database::create_in_memory().unwrap();
+    db.load::<Note>().unwrap();
+    db.save_with(&path, SaveOptions::default()).unwrap();
+
+    let result = db.save_with(
+        &path,
+        SaveOptions {
+            overwrite: false,
+            ..Default::default()
+        },
+    );
+    assert!(matches!(result, Err(silo::Error::TargetExists(p)) if p == path));
+
+    db.save_with(
+        &path,
+        SaveOptions {
+            overwrite: true,
+            backup_existing: true,
+        },
+    )
+    .unwrap();
+    let files = std::fs::read_dir(&dir).unwrap().count();
+    assert_eq!(files, 2);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_chrono_columns() {
+    use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+
+    use crate::conversions::ToSqlValueString;
+
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Appointment {
+        #[silo(primary)]
+        id: u32,
+        day: NaiveDate,
+        start: NaiveTime,
+        reminder: Option<NaiveDateTime>,
+    }
+
+    let first_day = NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+    let first = Appointment {
+        id: 1,
+        day: first_day,
+        start: NaiveTime::from_hms_milli_opt(9, 30, 0, 250).unwrap(),
+        reminder: Some(first_day.and_hms_opt(8, 0, 0).unwrap()),
+    };
+    let second = Appointment {
+        id: 2,
+        day: NaiveDate::from_ymd_opt(2024, 3, 1).unwrap(),
+        start: NaiveTime::from_hms_opt(14, 0, 0).unwrap(),
+        reminder: None,
+    };
+
+    let db = Database::create_in_memory().unwrap();
+    let appointments = db.load::<Appointment>().unwrap();
+    appointments.insert(first.clone()).unwrap();
+    appointments.insert(second.clone()).unwrap();
+
+    assert_eq!(
+        appointments.load_where(()).unwrap(),
+        vec![first.clone(), second.clone()]
+    );
+    let loaded = appointments
+        .load_where(AppointmentFilter {
+            day: first_day.convert_to_equals_filter(),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(loaded, vec![first]);
+    let loaded = appointments
+        .load_where(AppointmentFilter {
+            start: FieldFilter::greater_than(
+                NaiveTime::from_hms_opt(12, 0, 0)
+                    .unwrap()
+                    .to_sql_value_string(),
+            ),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(loaded, vec![second]);
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+fn test_uuid_columns() {
+    use uuid::NonNilUuid;
+
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Session {
+        #[silo(primary)]
+        id: NonNilUuid,
+        user: Uuid,
+    }
+
+    let first = Session {
+        id: NonNilUuid::new(Uuid::NAMESPACE_DNS).unwrap(),
+        user: Uuid::max(),
+    };
+    let second = Session {
+        id: NonNilUuid::new(Uuid::NAMESPACE_URL).unwrap(),
+        user: Uuid::nil(),
+    };
+
+    let db = Database::create_in_memory().unwrap();
+    let sessions = db.load::<Session>().unwrap();
+    sessions.insert(first.clone()).unwrap();
+    sessions.insert(second.clone()).unwrap();
+
+    assert_eq!(
+        sessions.load_where(()).unwrap(),
+        vec![first.clone(), second.clone()]
+    );
+    assert_eq!(sessions.load_where(second.id).unwrap(), vec![second]);
+    let loaded = sessions
+        .load_where(SessionFilter {
+            user: Uuid::max().convert_to_equals_filter(),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(loaded, vec![first]);
+}
+
+#[test]
+fn test_transparent_columns() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToColumns)]
+    #[silo(transparent)]
+    struct Email(String);
+
+    #[derive(Debug, Clone, PartialEq, Eq, ToColumns)]
+    #[silo(transparent)]
+    struct Karma {
+        points: i64,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct User {
+        #[silo(primary)]
+        email: Email,
+        backup_email: Option<Email>,
+        karma: Karma,
+    }
+
+    let column_names: Vec<_> = User::columns(None, false, false)
+        .into_iter()
+        .map(|c| c.name)
+        .collect();
+    assert_eq!(column_names, ["email", "backup_email", "karma"]);
+
+    let alice = User {
+        email: Email("alice@example.com".into()),
+        backup_email: None,
+        karma: Karma { points: 12 },
+    };
+    let bob = User {
+        email: Email("bob@example.com".into()),
+        backup_email: Some(Email("bob@example.org".into())),
+        karma: Karma { points: -3 },
+    };
+
+    let db = Database::create_in_memory().unwrap();
+    let users = db.load::<User>().unwrap();
+    users.insert(alice.clone()).unwrap();
+    users.insert(bob.clone()).unwrap();
+    assert!(!users.insert(alice.clone()).unwrap());
+
+    assert_eq!(
+        users.load_where(()).unwrap(),
+        vec![alice.clone(), bob.clone()]
+    );
+    assert_eq!(
+        users.load_where(bob.email.clone()).unwrap(),
+        vec![bob.clone()]
+    );
+    let loaded = users
+        .load_where(UserFilter {
+            karma: FieldFilter::greater_than(0),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(loaded, vec![alice.clone()]);
+
+    users
+        .update(
+            alice.email.clone(),
+            PartialUser {
+                backup_email: Some(Some(Email("alice@example.org".into()))),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    let loaded = users
+        .load_where(UserFilter {
+            backup_email: OptionalFilter::IsSomeAnd(FieldFilter::equals("alice@example.org")),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(loaded.len(), 1);
+    assert_eq!(loaded[0].email, alice.email);
+}
+
+#[test]
+fn test_validate_schema() {
+    #[derive(Debug, Clone, ToTable)]
+    struct Book {
+        #[silo(primary)]
+        isbn: String,
+        title: String,
+        pages: u32,
+    }
+
+    #[derive(Debug, Clone, ToTable)]
+    struct Shelf {
+        label: String,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    db.connection
+        .execute_batch(
+            "CREATE TABLE Book (\"isbn\" TEXT PRIMARY KEY, \"title\" INTEGER, \"author\" TEXT);",
+        )
+        .unwrap();
+    db.register::<Book>();
+    db.register::<Shelf>();
+
+    let mismatches = db.validate_schema().unwrap();
+    assert_eq!(
+        mismatches,
+        vec![
+            SchemaMismatch::WrongColumnType {
+                table: "Book",
+                column: "title".into(),
+                expected: "TEXT",
+                actual: "INTEGER".into(),
+            },
+            SchemaMismatch::MissingColumn {
+                table: "Book",
+                column: "pages".into(),
+            },
+            SchemaMismatch::UnexpectedColumn {
+                table: "Book",
+                column: "author".into(),
+            },
+            SchemaMismatch::MissingTable { table: "Shelf" },
+        ]
+    );
+
+    db.load::<Shelf>().unwrap();
+    assert_eq!(db.validate_schema().unwrap().len(), 3);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_json_columns() {
+    #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Credits {
+        artists: Vec<String>,
+        producer: Option<String>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, ToTable)]
+    struct Track {
+        #[silo(primary)]
+        id: u32,
+        #[silo(json)]
+        credits: Credits,
+        #[silo(json)]
+        tags: Vec<String>,
+    }
+
+    let column_names: Vec<_> = Track::columns(None, false, false)
+        .into_iter()
+        .map(|c| c.name)
+        .collect();
+    assert_eq!(column_names, ["id", "credits", "tags"]);
+
+    let track = Track {
+        id: 1,
+        credits: Credits {
+            artists: vec!["Alice".into(), "Bob".into()],
+            producer: None,
+        },
+        tags: vec!["jazz".into()],
+    };
+
+    let db = Database::create_in_memory().unwrap();
+    let tracks = db.load::<Track>().unwrap();
+    tracks.insert(track.clone()).unwrap();
+    assert_eq!(tracks.load_where(()).unwrap(), vec![track.clone()]);
+
+    tracks
+        .update(
+            1u32,
+            PartialTrack {
+                tags: Some(silo::json::Json(vec!["jazz".into(), "swing".into()])),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    let loaded = tracks
+        .load_where(TrackFilter {
+            tags: FieldFilter::equals(r#"["jazz","swing"]"#.to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(loaded.len(), 1);
+    assert_eq!(loaded[0].tags, ["jazz", "swing"]);
+    assert_eq!(loaded[0].credits, track.credits);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_unserializable_json_columns() {
+    use std::collections::BTreeMap;
+
+    #[derive(Debug, Clone, PartialEq, ToTable)]
+    struct Grid {
+        #[silo(primary)]
+        id: u32,
+        // JSON objects only have string keys.
+        #[silo(json)]
+        cells: BTreeMap<(u8, u8), String>,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let grids = db.load::<Grid>().unwrap();
+    let grid = Grid {
+        id: 1,
+        cells: BTreeMap::from([((0, 0), "start".into())]),
+    };
+    assert!(matches!(grids.insert(grid.clone()), Err(Error::Json(_))));
+    assert!(grids.load_where(()).unwrap().is_empty());
+
+    let empty = Grid {
+        id: 2,
+        cells: BTreeMap::new(),
+    };
+    assert!(grids.insert(empty.clone()).unwrap());
+    let loaded = grids
+        .load_where(GridFilter {
+            cells: silo::json::Json(grid.cells).convert_to_equals_filter(),
+            ..Default::default()
+        })
+        .unwrap();
+    assert!(loaded.is_empty());
+}
+
+#[test]
+fn test_negated_filters() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Book {
+        #[silo(primary)]
+        id: u32,
+        title: String,
+    }
+
+    let dune = Book {
+        id: 1,
+        title: "Dune".into(),
+    };
+    let emma = Book {
+        id: 2,
+        title: "Emma".into(),
+    };
+    let dracula = Book {
+        id: 3,
+        title: "Dracula".into(),
+    };
+
+    let db = Database::create_in_memory().unwrap();
+    let books = db.load::<Book>().unwrap();
+    books.insert(dune.clone()).unwrap();
+    books.insert(emma.clone()).unwrap();
+    books.insert(dracula.clone()).unwrap();
+
+    let loaded = books
+        .load_where(BookFilter::default().title_is_not(FieldFilter::equals("Dune")))
+        .unwrap();
+    assert_eq!(loaded, vec![emma.clone(), dracula.clone()]);
+
+    let loaded = books
+        .load_where(BookFilter::default().title_is_not(FieldFilter::contains(&"ra".into())))
+        .unwrap();
+    assert_eq!(loaded, vec![dune.clone(), emma.clone()]);
+
+    let loaded = books
+        .load_where(BookFilter::default().id_is_not(FieldFilter::not(FieldFilter::equals(2u32))))
+        .unwrap();
+    assert_eq!(loaded, vec![emma.clone()]);
+
+    // Negating nothing does not restrict the rows either.
+    let loaded = books
+        .load_where(BookFilter::default().title_is_not(FieldFilter::None))
+        .unwrap();
+    assert_eq!(loaded.len(), 3);
+}
+
+#[test]
+fn test_contains_filter() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Book {
+        #[silo(primary)]
+        id: u32,
+        title: String,
+        pages: u32,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let books = db.load::<Book>().unwrap();
+    for (id, title, pages) in [(1, "Dune", 412), (2, "Dracula", 418), (3, "Emma", 44)] {
+        books
+            .insert(Book {
+                id,
+                title: title.into(),
+                pages,
+            })
+            .unwrap();
+    }
+
+    let loaded = books
+        .load_where(BookFilter {
+            title: FieldFilter::contains(&"RA".into()),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(loaded.iter().map(|b| b.id).collect::<Vec<_>>(), vec![2]);
+
+    // Numbers have no contains filter, matching a part of one is explicit.
+    let loaded = books
+        .load_where(BookFilter {
+            pages: FieldFilter::equals(44u32),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(loaded.iter().map(|b| b.id).collect::<Vec<_>>(), vec![3]);
+}
+
+#[test]
+fn test_paginate() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Entry {
+        #[silo(primary)]
+        id: u32,
+        value: u32,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let entries = db.load::<Entry>().unwrap();
+    for id in 0..25 {
+        entries.insert(Entry { id, value: id % 2 }).unwrap();
+    }
+
+    let pages: Vec<_> = (0..4)
+        .map(|page| entries.paginate((), page, 10).unwrap())
+        .collect();
+    assert!(pages.iter().all(|p| p.total == 25 && p.page_count() == 3));
+    assert_eq!(
+        pages.iter().map(|p| p.items.len()).collect::<Vec<_>>(),
+        vec![10, 10, 5, 0]
+    );
+    assert_eq!(pages[1].page, 1);
+    assert_eq!(pages[1].items[0].id, 10);
+
+    let page = entries
+        .paginate(
+            EntryFilter {
+                value: FieldFilter::equals(1u32),
+                ..Default::default()
+            },
+            1,
+            10,
+        )
+        .unwrap();
+    assert_eq!(page.total, 12);
+    assert_eq!(
+        page.items.iter().map(|e| e.id).collect::<Vec<_>>(),
+        vec![21, 23]
+    );
+}
+
+#[test]
+fn test_filter_where() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Book {
+        #[silo(primary)]
+        id: u32,
+        title: String,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let books = db.load::<Book>().unwrap();
+    for (id, title) in [(1, "Dune"), (2, "Dracula"), (3, "Dr. No"), (4, "Emma")] {
+        books
+            .insert(Book {
+                id,
+                title: title.into(),
+            })
+            .unwrap();
+    }
+
+    let loaded = books
+        .filter_where(
+            BookFilter::default().title_starts_with("D"),
+            "LENGTH(title) > 5",
+            &[],
+        )
+        .unwrap();
+    assert_eq!(loaded.iter().map(|b| b.id).collect::<Vec<_>>(), vec![2, 3]);
+
+    // The parameters are bound after the ones of the filter.
+    let loaded = books
+        .filter_where(
+            BookFilter::default().id_is_not(FieldFilter::equals(3u32)),
+            "LENGTH(title) > ?",
+            &[&4],
+        )
+        .unwrap();
+    assert_eq!(loaded.iter().map(|b| b.id).collect::<Vec<_>>(), vec![2]);
+
+    let loaded = books
+        .filter_where(BookFilter::default(), "title LIKE ?", &[&"%m%"])
+        .unwrap();
+    assert_eq!(loaded.iter().map(|b| b.id).collect::<Vec<_>>(), vec![4]);
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+fn test_get_many() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Book {
+        #[silo(primary)]
+        id: u32,
+        title: String,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let books = db.load::<Book>().unwrap();
+    for id in 0..10 {
+        books
+            .insert(Book {
+                id,
+                title: format!("Volume {id}"),
+            })
+            .unwrap();
+    }
+
+    let loaded = books.get_many(vec![2u32, 5, 42, 9, 100]).unwrap();
+    assert_eq!(loaded.len(), 3);
+    assert_eq!(loaded[&2].title, "Volume 2");
+    assert_eq!(loaded[&5].title, "Volume 5");
+    assert_eq!(loaded[&9].title, "Volume 9");
+    assert!(!loaded.contains_key(&42));
+    assert!(books.get_many(Vec::<u32>::new()).unwrap().is_empty());
+
+    let persons = db.load::<Person>().unwrap();
+    let alice = Person {
+        id: Uuid::NAMESPACE_X500,
+        name: "Alice".into(),
+        ..Default::default()
+    };
+    persons.insert(alice.clone()).unwrap();
+    let loaded = persons
+        .get_many(vec![Uuid::NAMESPACE_X500, Uuid::NAMESPACE_DNS])
+        .unwrap();
+    assert_eq!(loaded.len(), 1);
+    assert_eq!(loaded[&alice.id], alice);
+}
+
+#[test]
+fn test_or_filter() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Movie {
+        #[silo(primary)]
+        id: u32,
+        title: String,
+        original_title: String,
+        year: u16,
+    }
+
+    let amelie = Movie {
+        id: 1,
+        title: "Amelie".into(),
+        original_title: "Le Fabuleux Destin d'Amelie Poulain".into(),
+        year: 2001,
+    };
+    let spirited_away = Movie {
+        id: 2,
+        title: "Spirited Away".into(),
+        original_title: "Sen to Chihiro no Kamikakushi".into(),
+        year: 2001,
+    };
+    let metropolis = Movie {
+        id: 3,
+        title: "Metropolis".into(),
+        original_title: "Metropolis".into(),
+        year: 1927,
+    };
+
+    let db = Database::create_in_memory().unwrap();
+    let movies = db.load::<Movie>().unwrap();
+    movies.insert(amelie.clone()).unwrap();
+    movies.insert(spirited_away.clone()).unwrap();
+    movies.insert(metropolis.clone()).unwrap();
+
+    let loaded = movies
+        .load_where(
+            MovieFilter {
+                title: FieldFilter::equals("Spirited Away"),
+                ..Default::default()
+            }
+            .or(MovieFilter {
+                original_title: FieldFilter::equals("Metropolis"),
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+    assert_eq!(loaded, vec![spirited_away.clone(), metropolis.clone()]);
+
+    // Every alternative is parenthesized, so the AND binds inside of it.
+    let loaded = movies
+        .load_where(
+            MovieFilter {
+                title: FieldFilter::equals("Metropolis"),
+                year: FieldFilter::greater_than(2000u16),
+                ..Default::default()
+            }
+            .or(MovieFilter {
+                original_title: FieldFilter::contains(&"Amelie".into()),
+                year: FieldFilter::greater_than(2000u16),
+                ..Default::default()
+            }),
+        )
+        .unwrap();
+    assert_eq!(loaded, vec![amelie.clone()]);
+
+    // An empty alternative matches every row.
+    let loaded = movies
+        .load_where(
+            MovieFilter {
+                id: FieldFilter::equals(1u32),
+                ..Default::default()
+            }
+            .or(()),
+        )
+        .unwrap();
+    assert_eq!(loaded.len(), 3);
+
+    let loaded = movies
+        .load_where(
+            MovieFilter {
+                year: FieldFilter::less_than(2000u16),
+                ..Default::default()
+            }
+            .or(2u32)
+            .or(1u32),
+        )
+        .unwrap();
+    assert_eq!(loaded.len(), 3);
+}
+
+#[test]
+fn test_unique_option_allows_many_nulls() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Movie {
+        #[silo(primary)]
+        id: u32,
+        #[silo(unique)]
+        imdb_id: Option<String>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Series {
+        #[silo(primary)]
+        id: u32,
+        #[silo(unique_index)]
+        imdb_id: Option<String>,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let movies = db.load::<Movie>().unwrap();
+    let series = db.load::<Series>().unwrap();
+    db.load::<Series>().unwrap();
+    for id in 0..3 {
+        assert!(movies.insert(Movie { id, imdb_id: None }).unwrap());
+        assert!(series.insert(Series { id, imdb_id: None }).unwrap());
+    }
+    let imdb_id = Some("tt0211915".to_string());
+    assert!(
+        movies
+            .insert(Movie {
+                id: 3,
+                imdb_id: imdb_id.clone(),
+            })
+            .unwrap()
+    );
+    assert!(
+        !movies
+            .insert(Movie {
+                id: 4,
+                imdb_id: imdb_id.clone(),
+            })
+            .unwrap()
+    );
+    assert!(
+        series
+            .insert(Series {
+                id: 3,
+                imdb_id: imdb_id.clone(),
+            })
+            .unwrap()
+    );
+    assert!(
+        !series
+            .insert(Series {
+                id: 4,
+                imdb_id: imdb_id.clone(),
+            })
+            .unwrap()
+    );
+    assert_eq!(movies.load_where(()).unwrap().len(), 4);
+    assert_eq!(series.load_where(()).unwrap().len(), 4);
+}
+
+#[test]
+fn test_bytes_are_stored_as_blob() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Attachment {
+        #[silo(primary)]
+        id: u32,
+        data: Vec<u8>,
+        thumbnail: Option<Vec<u8>>,
+    }
+
+    let columns = Attachment::columns(None, false, false);
+    assert_eq!(columns.len(), 3);
+    assert_eq!(columns[1].r#type.as_sql(), "BLOB");
+
+    let empty = Attachment {
+        id: 1,
+        data: Vec::new(),
+        thumbnail: None,
+    };
+    let small = Attachment {
+        id: 2,
+        data: vec![0, 1, 0xFF, b'\'', 0],
+        thumbnail: Some(vec![7; 3]),
+    };
+    let large = Attachment {
+        id: 3,
+        data: (0..1_000_000).map(|i| (i * 31 % 256) as u8).collect(),
+        thumbnail: Some(Vec::new()),
+    };
+
+    let db = Database::create_in_memory().unwrap();
+    let attachments = db.load::<Attachment>().unwrap();
+    attachments.insert(empty.clone()).unwrap();
+    attachments.insert(small.clone()).unwrap();
+    attachments.insert(large.clone()).unwrap();
+
+    assert_eq!(
+        attachments.load_where(()).unwrap(),
+        vec![empty.clone(), small.clone(), large.clone()]
+    );
+    let loaded = attachments
+        .load_where(AttachmentFilter {
+            data: FieldFilter::equals(small.data.clone()),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(loaded, vec![small.clone()]);
+}
+
+#[test]
+fn test_128_bit_integers() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Counter {
+        #[silo(primary)]
+        id: u128,
+        value: i128,
+    }
+
+    let counters = [
+        Counter { id: 0, value: 0 },
+        Counter {
+            id: u128::MAX,
+            value: i128::MIN,
+        },
+        Counter {
+            id: u64::MAX as u128 + 1,
+            value: -1,
+        },
+        Counter {
+            id: 7,
+            value: i128::MAX,
+        },
+    ];
+
+    let db = Database::create_in_memory().unwrap();
+    let table = db.load::<Counter>().unwrap();
+    for counter in &counters {
+        table.insert(counter.clone()).unwrap();
+    }
+    assert_eq!(table.load_where(()).unwrap(), counters);
+    assert_eq!(table.load_where(u128::MAX).unwrap(), [counters[1].clone()]);
+    assert_eq!(
+        table
+            .load_where(CounterFilter {
+                value: (-1i128).convert_to_equals_filter(),
+                ..Default::default()
+            })
+            .unwrap(),
+        [counters[2].clone()]
+    );
+
+    // The sign bit is flipped, so the blobs are ordered like the integers.
+    let negative = table
+        .load_where(CounterFilter {
+            value: FieldFilter::less_than(0i128.to_blob().to_vec()),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(negative, [counters[1].clone(), counters[2].clone()]);
+}
+
+#[test]
+fn test_char_columns() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Grade {
+        #[silo(primary)]
+        student: String,
+        letter: char,
+        symbol: Option<char>,
+    }
+
+    let alice = Grade {
+        student: "Alice".into(),
+        letter: 'A',
+        symbol: Some('★'),
+    };
+    let bob = Grade {
+        student: "Bob".into(),
+        letter: 'C',
+        symbol: None,
+    };
+
+    let db = Database::create_in_memory().unwrap();
+    let grades = db.load::<Grade>().unwrap();
+    grades.insert(alice.clone()).unwrap();
+    grades.insert(bob.clone()).unwrap();
+    assert_eq!(
+        grades.load_where(()).unwrap(),
+        vec![alice.clone(), bob.clone()]
+    );
+    let loaded = grades
+        .load_where(GradeFilter {
+            letter: 'C'.convert_to_equals_filter(),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(loaded, vec![bob.clone()]);
+
+    db.connection
+        .execute("UPDATE Grade SET letter = 'AB' WHERE student = 'Bob'", ())
+        .unwrap();
+    let mut statement = db
+        .connection
+        .prepare("SELECT letter FROM Grade WHERE student = 'Bob'")
+        .unwrap();
+    let error = statement
+        .query_row((), |r| {
+            Ok(<char as crate::ExtractFromRow>::try_from_row_simple(
+                "letter", r,
+            ))
+        })
+        .unwrap();
+    assert!(matches!(error, Err(crate::Error::IllFormattedColumn(..))));
+}
+
+#[test]
+fn test_statement_cache() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Entry {
+        #[silo(primary)]
+        id: u32,
+        value: u32,
+    }
+
+    for capacity in [0, 1, 16] {
+        let db = Database::create_in_memory().unwrap();
+        db.set_statement_cache_capacity(capacity);
+        let entries = db.load::<Entry>().unwrap();
+        for id in 0..10 {
+            entries.insert(Entry { id, value: id % 3 }).unwrap();
+            let loaded = entries
+                .load_where(EntryFilter {
+                    value: FieldFilter::equals(0u32),
+                    ..Default::default()
+                })
+                .unwrap();
+            assert_eq!(loaded.len() as u32, id / 3 + 1);
+        }
+    }
+}
+
+#[test]
+fn test_insert_reuses_statement() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Entry {
+        #[silo(primary)]
+        id: u32,
+        value: u32,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let entries = db.load::<Entry>().unwrap();
+    for id in 0..100 {
+        entries.insert(Entry { id, value: id * 2 }).unwrap();
+    }
+    // A statement, which was prepared anew for every insert, would have run
+    // only once.
+    let sql = crate::insert_sql::<Entry>("Entry".into(), EntryTable::INSERT_FAILURE_BEHAVIOR);
+    let statement = db.connection.prepare_cached(&sql).unwrap();
+    assert_eq!(statement.get_status(rusqlite::StatementStatus::Run), 100);
+}
+
+#[test]
+fn test_validate_insert() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Account {
+        #[silo(primary)]
+        id: u32,
+        #[silo(unique)]
+        email: String,
+    }
+
+    let alice = Account {
+        id: 1,
+        email: "alice@example.com".into(),
+    };
+    let mallory = Account {
+        id: 2,
+        email: "alice@example.com".into(),
+    };
+    let bob = Account {
+        id: 3,
+        email: "bob@example.com".into(),
+    };
+
+    let db = Database::create_in_memory().unwrap();
+    let accounts = db.load::<Account>().unwrap();
+    assert!(accounts.validate_insert(&alice).unwrap());
+    assert!(accounts.load_where(()).unwrap().is_empty());
+
+    accounts.insert(alice.clone()).unwrap();
+    assert!(!accounts.validate_insert(&mallory).unwrap());
+    assert!(accounts.validate_insert(&bob).unwrap());
+    assert_eq!(accounts.load_where(()).unwrap(), vec![alice.clone()]);
+}
+
+#[test]
+fn test_clear_and_drop_table() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Genre {
+        #[silo(primary)]
+        id: u32,
+        name: String,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let genres = db.load::<Genre>().unwrap();
+    for (id, name) in ["Drama", "Comedy", "Horror"].into_iter().enumerate() {
+        genres
+            .insert(Genre {
+                id: id as u32,
+                name: name.into(),
+            })
+            .unwrap();
+    }
+
+    assert_eq!(genres.clear().unwrap(), 3);
+    assert!(!genres.exists(()).unwrap());
+    assert_eq!(genres.clear().unwrap(), 0);
+
+    db.drop_table::<Genre>().unwrap();
+    assert!(!db.connection.table_exists(None, "Genre").unwrap());
+    assert!(db.validate_schema().unwrap().is_empty());
+    db.drop_table::<Genre>().unwrap();
+
+    let genres = db.load::<Genre>().unwrap();
+    assert!(genres.load_where(()).unwrap().is_empty());
+}
+
+#[test]
+fn test_query_as() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Genre {
+        #[silo(primary)]
+        id: u32,
+        name: String,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let genres = db.load::<Genre>().unwrap();
+    let all: Vec<_> = ["Drama", "Comedy", "Horror"]
+        .into_iter()
+        .enumerate()
+        .map(|(id, name)| Genre {
+            id: id as u32,
+            name: name.into(),
+        })
+        .collect();
+    for genre in &all {
+        genres.insert(genre.clone()).unwrap();
+    }
+
+    let loaded: Vec<Genre> = db
+        .query_as("SELECT * FROM Genre WHERE id > ?1", &[&0])
+        .unwrap();
+    assert_eq!(loaded, all[1..]);
+
+    let loaded: Vec<Genre> = db
+        .query_as(
+            "SELECT id, upper(name) AS name FROM Genre ORDER BY id DESC LIMIT 1",
+            &[],
+        )
+        .unwrap();
+    assert_eq!(loaded[0].name, "HORROR");
+
+    // A missing column is reported instead of panicking.
+    assert!(db.query_as::<Genre>("SELECT id FROM Genre", &[]).is_err());
+}
+
+#[test]
+fn test_find_one() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Coord {
+        x: i32,
+        y: i32,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let coords = db.load::<Coord>().unwrap();
+    for x in 0..5 {
+        coords.insert(Coord { x, y: x * x }).unwrap();
+    }
+
+    let found = coords
+        .find_one(CoordFilter {
+            y: FieldFilter::equals(9),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(found, Some(Coord { x: 3, y: 9 }));
+    let found = coords
+        .find_one(CoordFilter {
+            x: FieldFilter::greater_than(1),
+            ..Default::default()
+        })
+        .unwrap();
+    assert!(found.is_some_and(|c| c.x > 1));
+    let found = coords
+        .find_one(CoordFilter {
+            y: FieldFilter::equals(2),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(found, None);
+}
+
+#[test]
+fn test_duration_columns() {
+    use std::time::Duration;
+
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Job {
+        #[silo(primary)]
+        id: u32,
+        timeout: Duration,
+        elapsed: Option<Duration>,
+    }
+
+    let first = Job {
+        id: 1,
+        timeout: Duration::from_millis(1500),
+        elapsed: Some(Duration::new(3, 999_999_999)),
+    };
+    let second = Job {
+        id: 2,
+        timeout: Duration::ZERO,
+        elapsed: None,
+    };
+
+    let db = Database::create_in_memory().unwrap();
+    let jobs = db.load::<Job>().unwrap();
+    jobs.insert(first.clone()).unwrap();
+    jobs.insert(second.clone()).unwrap();
+    assert_eq!(
+        jobs.load_where(()).unwrap(),
+        vec![first.clone(), second.clone()]
+    );
+    let loaded = jobs
+        .load_where(JobFilter {
+            timeout: Duration::from_millis(1500).convert_to_equals_filter(),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(loaded, vec![first.clone()]);
+
+    db.connection
+        .execute("UPDATE Job SET timeout = -1 WHERE id = 2", ())
+        .unwrap();
+    let mut statement = db
+        .connection
+        .prepare("SELECT timeout FROM Job WHERE id = 2")
+        .unwrap();
+    let error = statement
+        .query_row((), |r| {
+            Ok(<Duration as crate::ExtractFromRow>::try_from_row_simple(
+                "timeout", r,
+            ))
+        })
+        .unwrap();
+    assert!(matches!(error, Err(crate::Error::IllFormattedColumn(..))));
+}
+
+#[test]
+fn test_nocase_unique() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct User {
+        #[silo(primary)]
+        id: u32,
+        #[silo(unique)]
+        #[silo(nocase)]
+        name: String,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let users = db.load::<User>().unwrap();
+    assert!(
+        users
+            .insert(User {
+                id: 1,
+                name: "Alice".into(),
+            })
+            .unwrap()
+    );
+    assert!(
+        !users
+            .insert(User {
+                id: 2,
+                name: "alice".into(),
+            })
+            .unwrap()
+    );
+    assert!(
+        users
+            .insert(User {
+                id: 3,
+                name: "Bob".into(),
+            })
+            .unwrap()
+    );
+    let loaded = users
+        .load_where(UserFilter {
+            name: FieldFilter::equals("ALICE"),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(loaded.len(), 1);
+    assert_eq!(loaded[0].id, 1);
+}
+
+#[test]
+fn test_insert_with_failure_behavior() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Setting {
+        #[silo(primary)]
+        key: String,
+        value: String,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    #[silo(replace)]
+    struct Cache {
+        #[silo(primary)]
+        key: String,
+        value: String,
+    }
+
+    let setting = |value: &str| Setting {
+        key: "theme".into(),
+        value: value.into(),
+    };
+
+    let db = Database::create_in_memory().unwrap();
+    let settings = db.load::<Setting>().unwrap();
+    assert!(settings.insert(setting("dark")).unwrap());
+    assert!(!settings.insert(setting("light")).unwrap());
+    assert!(
+        !settings
+            .insert_with(setting("light"), SqlFailureBehavior::Ignore)
+            .unwrap()
+    );
+    assert_eq!(settings.load_where(()).unwrap(), vec![setting("dark")]);
+    assert!(
+        settings
+            .insert_with(setting("light"), SqlFailureBehavior::Replace)
+            .unwrap()
+    );
+    assert_eq!(settings.load_where(()).unwrap(), vec![setting("light")]);
+
+    let cache = db.load::<Cache>().unwrap();
+    for value in ["a", "b"] {
+        assert!(
+            cache
+                .insert(Cache {
+                    key: "k".into(),
+                    value: value.into(),
+                })
+                .unwrap()
+        );
+    }
+    assert_eq!(cache.load_where(()).unwrap()[0].value, "b");
+}
+
+#[test]
+fn test_default_adds_missing_column() {
+    mod v1 {
+        use super::*;
+
+        #[derive(Debug, Clone, ToTable)]
+        pub struct Measurement {
+            pub value: f64,
+        }
+    }
+
+    mod v2 {
+        use super::*;
+
+        #[derive(Debug, Clone, PartialEq, ToTable)]
+        pub struct Measurement {
+            pub value: f64,
+            #[silo(default = 55)]
+            pub offset: i32,
+            #[silo(default = "m")]
+            pub unit: String,
+            #[silo(default = -1.5)]
+            pub scale: f64,
+            // Only the default itself may contain the keyword.
+            #[silo(default = " DEFAULT ")]
+            pub label: String,
+        }
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let old = db.load::<v1::Measurement>().unwrap();
+    old.insert(v1::Measurement { value: 1.0 }).unwrap();
+
+    let new = db.load::<v2::Measurement>().unwrap();
+    new.insert(v2::Measurement {
+        value: 2.0,
+        offset: 3,
+        unit: "cm".into(),
+        scale: 1.0,
+        label: "new".into(),
+    })
+    .unwrap();
+    assert_eq!(
+        new.load_where(()).unwrap(),
+        vec![
+            v2::Measurement {
+                value: 1.0,
+                offset: 55,
+                unit: "m".into(),
+                scale: -1.5,
+                label: " DEFAULT ".into(),
+            },
+            v2::Measurement {
+                value: 2.0,
+                offset: 3,
+                unit: "cm".into(),
+                scale: 1.0,
+                label: "new".into(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_nan_filters() {
+    #[derive(Debug, Clone, PartialEq, ToTable)]
+    struct Reading {
+        #[silo(primary)]
+        id: u32,
+        value: f64,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let readings = db.load::<Reading>().unwrap();
+    assert!(
+        !readings
+            .exists(ReadingFilter::default().value_is_nan())
+            .unwrap()
+    );
+    readings.insert(Reading { id: 1, value: 1.5 }).unwrap();
+    readings
+        .insert(Reading {
+            id: 2,
+            value: f64::NAN,
+        })
+        .unwrap();
+
+    assert!(
+        readings
+            .exists(ReadingFilter::default().value_is_nan())
+            .unwrap()
+    );
+    assert!(
+        readings
+            .exists(ReadingFilter {
+                id: 2u32.convert_to_equals_filter(),
+                value: f64::NAN.convert_to_equals_filter(),
+                ..Default::default()
+            })
+            .unwrap()
+    );
+    assert!(
+        !readings
+            .exists(ReadingFilter {
+                id: 1u32.convert_to_equals_filter(),
+                value: FieldFilter::greater_than(f64::NAN),
+                ..Default::default()
+            })
+            .unwrap()
+    );
+    assert_eq!(
+        readings
+            .load_where(ReadingFilter::default().value_is_not(FieldFilter::<f64>::is_nan()))
+            .unwrap(),
+        vec![Reading { id: 1, value: 1.5 }]
+    );
+}
+
+#[test]
+fn test_open_readonly() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Book {
+        #[silo(primary)]
+        isbn: String,
+        pages: u32,
+    }
+
+    #[derive(Debug, Clone, ToTable)]
+    struct Shelf {
+        label: String,
+    }
+
+    let dir = std::env::temp_dir().join(format!("silo-test-readonly-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("books.db");
+    {
+        let db = Database::open(&path).unwrap();
+        let books = db.load::<Book>().unwrap();
+        for (isbn, pages) in [("a", 120), ("b", 480)] {
+            books
+                .insert(Book {
+                    isbn: isbn.into(),
+                    pages,
+                })
+                .unwrap();
+        }
+    }
+
+    let db = Database::open_readonly(&path).unwrap();
+    let books = db.load::<Book>().unwrap();
+    assert_eq!(
+        books
+            .load_where(BookFilter {
+                pages: FieldFilter::greater_than(200u32),
+                ..Default::default()
+            })
+            .unwrap(),
+        vec![Book {
+            isbn: "b".into(),
+            pages: 480,
+        }]
+    );
+    assert!(
+        books
+            .insert(Book {
+                isbn: "c".into(),
+                pages: 1,
+            })
+            .is_err()
+    );
+    assert!(matches!(
+        db.load::<Shelf>(),
+        Err(silo::Error::MissingTable(_))
+    ));
+    drop(db);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_open_with_pragmas() {
+    #[derive(Debug, Clone, Default, ToTable)]
+    struct Note {
+        text: String,
+    }
+
+    let dir = std::env::temp_dir().join(format!("silo-test-pragmas-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let db = Database::open_with_pragmas(
+        dir.join("pragmas.db"),
+        Pragmas {
+            wal: true,
+            foreign_keys: true,
+            synchronous_normal: true,
+        },
+    )
+    .unwrap();
+    let pragma = |name: &str| {
+        db.connection
+            .query_row(&format!("PRAGMA {name}"), (), |r| {
+                r.get::<_, rusqlite::types::Value>(0)
+            })
+            .unwrap()
+    };
+    assert_eq!(
+        pragma("journal_mode"),
+        rusqlite::types::Value::Text("wal".into())
+    );
+    assert_eq!(pragma("foreign_keys"), rusqlite::types::Value::Integer(1));
+    assert_eq!(pragma("synchronous"), rusqlite::types::Value::Integer(1));
+
+    let notes = db.load::<Note>().unwrap();
+    notes.insert(Note::default()).unwrap();
+    assert_eq!(notes.load_where(()).unwrap().len(), 1);
+    drop(db);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_references() {
+    #[derive(Debug, Clone, ToTable)]
+    struct Author {
+        #[silo(primary)]
+        id: u32,
+        name: String,
+    }
+
+    #[derive(Debug, Clone, ToTable)]
+    struct Book {
+        #[silo(primary)]
+        id: u32,
+        #[silo(references = Author)]
+        author: u32,
+    }
+
+    let columns = Book::columns(None, false, false);
+    assert_eq!(columns[0].references.as_deref(), None);
+    assert_eq!(columns[1].references.as_deref(), Some("Author"));
+
+    let db = Database::create_in_memory().unwrap();
+    db.connection
+        .execute_batch("PRAGMA foreign_keys=ON")
+        .unwrap();
+    let authors = db.load::<Author>().unwrap();
+    let books = db.load::<Book>().unwrap();
+    authors
+        .insert(Author {
+            id: 1,
+            name: "Ursula".into(),
+        })
+        .unwrap();
+    assert!(books.insert(Book { id: 1, author: 1 }).unwrap());
+    assert!(!books.insert(Book { id: 2, author: 7 }).unwrap());
+    assert_eq!(books.load_where(()).unwrap().len(), 1);
+}
+
+#[test]
+fn test_transparent_integer_primary_key() {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, ToColumns)]
+    #[silo(transparent)]
+    struct UserId(u32);
+
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Account {
+        #[silo(primary)]
+        id: UserId,
+        name: String,
+    }
+
+    let columns = Account::columns(None, false, false);
+    assert_eq!(columns[0].name, "id");
+    assert_eq!(columns[0].r#type, crate::SqlColumnType::Integer);
+    assert!(columns[0].is_primary);
+
+    let db = Database::create_in_memory().unwrap();
+    let accounts = db.load::<Account>().unwrap();
+    let account = Account {
+        id: UserId(4),
+        name: "root".into(),
+    };
+    accounts.insert(account.clone()).unwrap();
+    assert_eq!(accounts.load_where(UserId(4)).unwrap(), vec![account]);
+}
+
+#[test]
+fn test_skip_insert_and_skip_select() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Post {
+        #[silo(primary)]
+        id: u32,
+        #[silo(skip_insert)]
+        #[silo(default = 7)]
+        views: u32,
+        #[silo(skip_select)]
+        draft: String,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let posts = db.load::<Post>().unwrap();
+    let post = Post {
+        id: 1,
+        views: 100,
+        draft: "secret".into(),
+    };
+    assert!(posts.insert(post.clone()).unwrap());
+    assert!(posts.upsert(post, &[]).unwrap());
+
+    assert_eq!(
+        posts.load_where(()).unwrap(),
+        vec![Post {
+            id: 1,
+            views: 7,
+            draft: String::new(),
+        }]
+    );
+    let draft: String = db
+        .connection
+        .query_row("SELECT draft FROM Post", (), |r| r.get(0))
+        .unwrap();
+    assert_eq!(draft, "secret");
+}
+
+#[test]
+fn test_distinct_values() {
+    #[derive(Debug, Clone, ToTable)]
+    struct Film {
+        #[silo(primary)]
+        id: u32,
+        genre: String,
+        year: u32,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let films = db.load::<Film>().unwrap();
+    for (id, genre, year) in [
+        (1, "drama", 1999),
+        (2, "comedy", 2004),
+        (3, "drama", 2004),
+        (4, "horror", 1999),
+        (5, "comedy", 1999),
+    ] {
+        films
+            .insert(Film {
+                id,
+                genre: genre.into(),
+                year,
+            })
+            .unwrap();
+    }
+
+    let mut genres: Vec<String> = films
+        .distinct_values("genre", FilmFilter::default())
+        .unwrap();
+    genres.sort();
+    assert_eq!(genres, ["comedy", "drama", "horror"]);
+    let mut genres: Vec<String> = films
+        .distinct_values(
+            "genre",
+            FilmFilter {
+                year: 2004u32.convert_to_equals_filter(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    genres.sort();
+    assert_eq!(genres, ["comedy", "drama"]);
+    assert_eq!(films.count_distinct("year").unwrap(), 2);
+
+    assert!(matches!(
+        films.distinct_values::<String>("rating", FilmFilter::default()),
+        Err(silo::Error::MissingColumn(_))
+    ));
+    assert!(films.count_distinct("rating").is_err());
+}
+
+#[test]
+fn test_distinct_values_of_split_filter() {
+    #[derive(Debug, Clone, PartialEq, ToTable)]
+    struct Film {
+        #[silo(primary)]
+        id: u32,
+        genre: String,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    db.set_variable_limit(2).unwrap();
+    let films = db.load::<Film>().unwrap();
+    for (id, genre) in [(1, "drama"), (2, "comedy"), (3, "drama"), (4, "comedy")] {
+        films
+            .insert(Film {
+                id,
+                genre: genre.into(),
+            })
+            .unwrap();
+    }
+    let all = || FilmFilter {
+        id: FieldFilter::is_one_of(vec![1u32, 2, 3, 4]),
+        ..Default::default()
+    };
+
+    let mut genres: Vec<String> = films.distinct_values("genre", all()).unwrap();
+    genres.sort();
+    assert_eq!(genres, ["comedy", "drama"]);
+
+    assert!(matches!(
+        films.load_where_ordered(all(), &GenericOrder::default().asc("id")),
+        Err(Error::TooManyParameters(4, 2))
+    ));
+    assert!(matches!(
+        films.paginate(all(), 0, 10),
+        Err(Error::TooManyParameters(4, 2))
+    ));
+}
+
+#[test]
+fn test_aggregate() {
+    #[derive(Debug, Clone, ToTable)]
+    struct Production {
+        #[silo(primary)]
+        id: u32,
+        studio: String,
+        budget: i64,
+        rating: Option<f64>,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let productions = db.load::<Production>().unwrap();
+    for (id, studio, budget, rating) in [
+        (1, "north", 100, Some(6.0)),
+        (2, "north", 300, None),
+        (3, "south", 50, Some(8.0)),
+        (4, "north", 200, Some(7.0)),
+    ] {
+        productions
+            .insert(Production {
+                id,
+                studio: studio.into(),
+                budget,
+                rating,
+            })
+            .unwrap();
+    }
+
+    let north = || ProductionFilter {
+        studio: "north".to_string().convert_to_equals_filter(),
+        ..Default::default()
+    };
+    assert_eq!(
+        productions
+            .aggregate("budget", Aggregate::Sum, north())
+            .unwrap(),
+        Some(600.0)
+    );
+    assert_eq!(
+        productions
+            .aggregate("budget", Aggregate::Avg, north())
+            .unwrap(),
+        Some(200.0)
+    );
+    assert_eq!(
+        productions
+            .aggregate("rating", Aggregate::Avg, north())
+            .unwrap(),
+        Some(6.5)
+    );
+    assert_eq!(
+        productions
+            .aggregate("budget", Aggregate::Max, ProductionFilter::default())
+            .unwrap(),
+        Some(300.0)
+    );
+    let split = ProductionFilter {
+        id: FieldFilter::is_one_of(1..=4u32),
+        ..Default::default()
+    };
+    db.connection
+        .set_limit(rusqlite::limits::Limit::SQLITE_LIMIT_VARIABLE_NUMBER, 1)
+        .unwrap();
+    assert_eq!(
+        productions
+            .aggregate("budget", Aggregate::Avg, split.clone())
+            .unwrap(),
+        Some(162.5)
+    );
+    assert_eq!(
+        productions
+            .aggregate("budget", Aggregate::Min, split)
+            .unwrap(),
+        Some(50.0)
+    );
+    assert_eq!(
+        productions
+            .aggregate(
+                "budget",
+                Aggregate::Sum,
+                ProductionFilter {
+                    studio: "west".to_string().convert_to_equals_filter(),
+                    ..Default::default()
+                }
+            )
+            .unwrap(),
+        None
+    );
+    assert!(
+        productions
+            .aggregate("studio", Aggregate::Sum, ProductionFilter::default())
+            .is_err()
+    );
+}
+
+#[test]
+fn test_update_sets_null() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Show {
+        #[silo(primary)]
+        id: u32,
+        title: String,
+        overview: Option<String>,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let shows = db.load::<Show>().unwrap();
+    shows
+        .insert(Show {
+            id: 1,
+            title: "Pilot".into(),
+            overview: Some("Everything starts.".into()),
+        })
+        .unwrap();
+
+    // Leaving the field at `None` keeps the column as it is.
+    shows
+        .update(
+            1,
+            PartialShow {
+                title: Some("Pilot (remastered)".into()),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        shows.load_where(1).unwrap()[0].overview.as_deref(),
+        Some("Everything starts.")
+    );
+
+    shows
+        .update(
+            1,
+            PartialShow {
+                overview: Some(None),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        shows.load_where(1).unwrap(),
+        vec![Show {
+            id: 1,
+            title: "Pilot (remastered)".into(),
+            overview: None,
+        }]
+    );
+}
+
+#[test]
+fn test_load_where_ordered() {
+    #[derive(Debug, Clone, PartialEq, ToColumns)]
+    struct Movie {
+        title: String,
+        year: u32,
+    }
+
+    #[derive(Debug, Clone, PartialEq, ToTable)]
+    struct MovieWithRatings {
+        #[silo(primary)]
+        id: u32,
+        movie: Movie,
+        rating: Option<f64>,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let movies = db.load::<MovieWithRatings>().unwrap();
+    for (id, title, year, rating) in [
+        (1, "Heat", 1995, Some(8.3)),
+        (2, "Alien", 1979, None),
+        (3, "Casablanca", 1942, Some(8.5)),
+        (4, "Brazil", 1985, Some(7.9)),
+    ] {
+        movies
+            .insert(MovieWithRatings {
+                id,
+                movie: Movie {
+                    title: title.into(),
+                    year,
+                },
+                rating,
+            })
+            .unwrap();
+    }
+
+    let ids = |order: &GenericOrder| {
+        movies
+            .load_where_ordered((), order)
+            .unwrap()
+            .into_iter()
+            .map(|m| m.id)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        ids(&GenericOrder::default().asc(column_name_of!(MovieWithRatings, movie.title))),
+        [2, 4, 3, 1]
+    );
+    assert_eq!(
+        ids(&GenericOrder::default().desc(column_name_of!(MovieWithRatings, movie.year))),
+        [1, 4, 2, 3]
+    );
+    assert_eq!(
+        ids(&GenericOrder::default().add(
+            "rating",
+            Ordering {
+                asc_desc: Some(OrderingAscDesc::Descending),
+                nulls: Some(OrderingNulls::NullsFirst),
+            }
+        )),
+        [2, 3, 1, 4]
+    );
+    assert!(
+        movies
+            .load_where_ordered((), &GenericOrder::default().asc("movie"))
+            .is_err()
+    );
+}
+
+#[test]
+fn test_delete_returning() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Member {
+        #[silo(primary)]
+        name: String,
+        age: u32,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let members = db.load::<Member>().unwrap();
+    let member = |name: &str, age: u32| Member {
+        name: name.into(),
+        age,
+    };
+    for (name, age) in [("Ada", 36), ("Bob", 17), ("Cy", 15), ("Dee", 52)] {
+        members.insert(member(name, age)).unwrap();
+    }
+
+    let minors = || MemberFilter {
+        age: FieldFilter::less_than(18u32),
+        ..Default::default()
+    };
+    let deleted = members.delete_returning(minors()).unwrap();
+    assert_eq!(deleted, vec![member("Bob", 17), member("Cy", 15)]);
+    assert_eq!(
+        members.load_where(()).unwrap(),
+        vec![member("Ada", 36), member("Dee", 52)]
+    );
+    assert!(members.delete_returning(minors()).unwrap().is_empty());
+
+    db.connection
+        .set_limit(rusqlite::limits::Limit::SQLITE_LIMIT_VARIABLE_NUMBER, 1)
+        .unwrap();
+    let deleted = members
+        .delete(MemberFilter {
+            name: FieldFilter::is_one_of(["Ada", "Dee", "Eve"]),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(deleted, 2);
+    assert!(members.load_where(()).unwrap().is_empty());
+}
+
+#[test]
+fn test_drain_duplicates() {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, ToTable)]
+    struct Coord {
+        x: i32,
+        y: i32,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let coords = db.load::<Coord>().unwrap();
+    for (x, y) in [(1, 1), (1, 1), (2, 2), (1, 1)] {
+        coords.insert(Coord { x, y }).unwrap();
+    }
+
+    let mut taken = 0;
+    let drained = coords
+        .drain(CoordFilter::default(), |c| {
+            if c.x == 1 && taken < 2 {
+                taken += 1;
+                true
+            } else {
+                false
+            }
+        })
+        .unwrap();
+    assert_eq!(drained, [Coord { x: 1, y: 1 }; 2]);
+    assert_eq!(
+        coords.load_where(()).unwrap(),
+        [Coord { x: 2, y: 2 }, Coord { x: 1, y: 1 }]
+    );
+
+    let drained = coords.drain(CoordFilter::default(), |_| false).unwrap();
+    assert!(drained.is_empty());
+    assert_eq!(coords.load_where(()).unwrap().len(), 2);
+}
+
+#[test]
+fn test_introspect() {
+    #[derive(Debug, Clone, ToTable)]
+    struct Genre {
+        #[silo(primary)]
+        id: u32,
+        #[silo(unique)]
+        name: String,
+        #[silo(index)]
+        rank: Option<f64>,
+        #[silo(default = "x")]
+        icon: String,
+        #[silo(references = Genre)]
+        parent: Option<u32>,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    db.load::<Genre>().unwrap();
+    let columns = db.introspect("Genre").unwrap();
+    let names: Vec<_> = columns.iter().map(|c| c.name.as_ref()).collect();
+    assert_eq!(names, ["id", "name", "rank", "icon", "parent"]);
+    let types: Vec<_> = columns.iter().map(|c| c.r#type).collect();
+    assert_eq!(
+        types,
+        [
+            crate::SqlColumnType::Integer,
+            crate::SqlColumnType::Text,
+            crate::SqlColumnType::Float,
+            crate::SqlColumnType::Text,
+            crate::SqlColumnType::Integer,
+        ]
+    );
+    assert!(columns[0].is_primary);
+    assert!(!columns[1].is_primary);
+    assert!(columns[1].is_unique);
+    assert!(columns[2].is_indexed);
+    assert_eq!(columns[3].default.as_deref(), Some("'x'"));
+    assert_eq!(columns[4].references.as_deref(), Some("Genre"));
+
+    assert!(db.introspect("Missing").unwrap().is_empty());
+}
+
+#[test]
+fn test_malformed_row_returns_error() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Episode {
+        #[silo(primary)]
+        id: u32,
+        season: u32,
+        title: String,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let episodes = db.load::<Episode>().unwrap();
+    episodes
+        .insert(Episode {
+            id: 1,
+            season: 1,
+            title: "Pilot".into(),
+        })
+        .unwrap();
+    db.connection
+        .execute(
+            "INSERT INTO Episode (id, season, title) VALUES (2, 'two', 'Lost')",
+            (),
+        )
+        .unwrap();
+
+    assert!(matches!(
+        episodes.load_where(()),
+        Err(silo::Error::IncompleteRow(_))
+    ));
+    assert!(matches!(
+        episodes.find_one(2),
+        Err(silo::Error::IncompleteRow(_))
+    ));
+    assert!(matches!(
+        episodes.load_where_with_rowid(()),
+        Err(silo::Error::IncompleteRow(_))
+    ));
+    assert_eq!(
+        episodes.load_where(1).unwrap(),
+        vec![Episode {
+            id: 1,
+            season: 1,
+            title: "Pilot".into(),
+        }]
+    );
+}
+
+#[test]
+fn test_starts_with_and_ends_with() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Product {
+        #[silo(primary)]
+        id: u32,
+        name: String,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let products = db.load::<Product>().unwrap();
+    let names = [
+        "100% cotton",
+        "100 grams",
+        "Cotton_shirt",
+        "CottonXshirt",
+        "Shirt 50%",
+        "Shirt 50 off",
+        "O'Brien's",
+    ];
+    for (id, name) in names.iter().enumerate() {
+        products
+            .insert(Product {
+                id: id as u32,
+                name: name.to_string(),
+            })
+            .unwrap();
+    }
+    let load_names = |filter: ProductFilter| {
+        products
+            .load_where(filter)
+            .unwrap()
+            .into_iter()
+            .map(|p| p.name)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        load_names(ProductFilter::default().name_starts_with("100%")),
+        vec!["100% cotton"]
+    );
+    assert_eq!(
+        load_names(ProductFilter::default().name_starts_with("100")),
+        vec!["100% cotton", "100 grams"]
+    );
+    assert_eq!(
+        load_names(ProductFilter::default().name_starts_with("Cotton_")),
+        vec!["Cotton_shirt"]
+    );
+    assert_eq!(
+        load_names(ProductFilter::default().name_ends_with("50%")),
+        vec!["Shirt 50%"]
+    );
+    assert_eq!(
+        load_names(ProductFilter::default().name_ends_with("'s")),
+        vec!["O'Brien's"]
+    );
+    assert_eq!(
+        load_names(ProductFilter::default().name_is_not(FieldFilter::starts_with("Shirt"))),
+        vec![
+            "100% cotton",
+            "100 grams",
+            "Cotton_shirt",
+            "CottonXshirt",
+            "O'Brien's"
+        ]
+    );
+}
+
+#[test]
+fn test_glob_filter() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Movie {
+        #[silo(primary)]
+        id: u32,
+        title: String,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let movies = db.load::<Movie>().unwrap();
+    for (id, title) in ["Star Wars", "Lone Star", "A star is born", "Arrival"]
+        .into_iter()
+        .enumerate()
+    {
+        movies
+            .insert(Movie {
+                id: id as u32,
+                title: title.into(),
+            })
+            .unwrap();
+    }
+    let load_titles = |filter: MovieFilter| {
+        movies
+            .load_where(filter)
+            .unwrap()
+            .into_iter()
+            .map(|m| m.title)
+            .collect::<Vec<_>>()
+    };
+
+    // Unlike LIKE, GLOB is case-sensitive.
+    assert_eq!(
+        load_titles(MovieFilter::default().title_glob("*Star*")),
+        vec!["Star Wars", "Lone Star"]
+    );
+    assert_eq!(
+        load_titles(MovieFilter::default().title_glob("?rrival")),
+        vec!["Arrival"]
+    );
+    assert_eq!(
+        load_titles(MovieFilter::default().title_glob("*[sS]tar")),
+        vec!["Lone Star"]
+    );
+}
+
+#[cfg(feature = "regex")]
+#[test]
+fn test_regex_filter() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Movie {
+        #[silo(primary)]
+        id: u32,
+        title: String,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let movies = db.load::<Movie>().unwrap();
+    for (id, title) in ["The Matrix", "Theodore Rex", "Into the Wild", "the end"]
+        .into_iter()
+        .enumerate()
+    {
+        movies
+            .insert(Movie {
+                id: id as u32,
+                title: title.into(),
+            })
+            .unwrap();
+    }
+    let load_titles = |filter: MovieFilter| {
+        movies
+            .load_where(filter)
+            .unwrap()
+            .into_iter()
+            .map(|m| m.title)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        load_titles(MovieFilter::default().title_matches("^The")),
+        vec!["The Matrix", "Theodore Rex"]
+    );
+    assert_eq!(
+        load_titles(MovieFilter::default().title_matches(r"^The\b")),
+        vec!["The Matrix"]
+    );
+    assert!(
+        movies
+            .load_where(MovieFilter::default().title_matches("("))
+            .is_err()
+    );
+}
+
+#[test]
+fn test_vacuum_and_optimize() {
+    #[derive(Debug, Clone, ToTable)]
+    struct Log {
+        #[silo(primary)]
+        id: u32,
+        message: String,
+    }
+
+    let dir = std::env::temp_dir().join(format!("silo-test-vacuum-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("vacuum.db");
+    let db = Database::open(&path).unwrap();
+    let logs = db.load::<Log>().unwrap();
+    for id in 0..2000 {
+        logs.insert(Log {
+            id,
+            message: "x".repeat(200),
+        })
+        .unwrap();
+    }
+    let size_before = std::fs::metadata(&path).unwrap().len();
+    logs.delete(LogFilter::default().id_is_not(FieldFilter::less_than(10u32)))
+        .unwrap();
+    db.vacuum().unwrap();
+    db.optimize().unwrap();
+    assert!(std::fs::metadata(&path).unwrap().len() < size_before);
+    assert_eq!(logs.load_where(()).unwrap().len(), 10);
+    drop(db);
+
+    let db = Database::create_in_memory().unwrap();
+    db.vacuum().unwrap();
+    db.optimize().unwrap();
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_run_migrations() {
+    fn create_log(connection: &rusqlite::Connection) -> Result<(), Error> {
+        connection.execute("CREATE TABLE log (step INTEGER)", ())?;
+        Ok(())
+    }
+
+    fn log_step(connection: &rusqlite::Connection) -> Result<(), Error> {
+        connection.execute("INSERT INTO log (step) VALUES (2)", ())?;
+        Ok(())
+    }
+
+    fn fail(connection: &rusqlite::Connection) -> Result<(), Error> {
+        connection.execute("INSERT INTO log (step) VALUES (3)", ())?;
+        Err(Error::CouldNotMigrate("step 3".into()))
+    }
+
+    let migrations = [
+        Migration {
+            version: 2,
+            up: log_step,
+        },
+        Migration {
+            version: 1,
+            up: create_log,
+        },
+    ];
+    let steps = |db: &Database| -> Vec<i64> {
+        let mut statement = db.connection.prepare("SELECT step FROM log").unwrap();
+        statement
+            .query_map((), |r| r.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap()
+    };
+
+    let dir = std::env::temp_dir().join(format!("silo-test-migrations-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("migrations.db");
+    let db = Database::open(&path).unwrap();
+    assert_eq!(db.run_migrations(&migrations).unwrap(), 2);
+    assert_eq!(db.run_migrations(&migrations).unwrap(), 0);
+    assert_eq!(steps(&db), vec![2]);
+    drop(db);
+
+    let db = Database::open(&path).unwrap();
+    assert_eq!(db.run_migrations(&migrations).unwrap(), 0);
+    assert_eq!(steps(&db), vec![2]);
+
+    // A failed migration is rolled back and not recorded.
+    let failing = Migration {
+        version: 3,
+        up: fail,
+    };
+    assert!(db.run_migrations(&[migrations[0], failing]).is_err());
+    assert_eq!(steps(&db), vec![2]);
+    assert!(db.run_migrations(&[failing]).is_err());
+    drop(db);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_autoincrement() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Comment {
+        #[silo(primary)]
+        #[silo(autoincrement)]
+        id: i64,
+        #[silo(unique)]
+        text: String,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let comments = db.load::<Comment>().unwrap();
+    let first = comments
+        .insert_returning_id(Comment {
+            id: 0,
+            text: "First!".into(),
+        })
+        .unwrap();
+    let second = comments
+        .insert_returning_id(Comment {
+            id: 0,
+            text: "Second".into(),
+        })
+        .unwrap();
+    assert_eq!(first, Some(1));
+    assert_eq!(second, Some(2));
+    // The placeholder id is ignored, so the unique text conflicts.
+    assert_eq!(
+        comments
+            .insert_returning_id(Comment {
+                id: 1,
+                text: "Second".into(),
+            })
+            .unwrap(),
+        None
+    );
+    assert_eq!(
+        comments.load_where(2).unwrap(),
+        vec![Comment {
+            id: 2,
+            text: "Second".into(),
+        }]
+    );
+
+    // Unlike plain rowids, ids of deleted rows are not reused.
+    comments.delete(2).unwrap();
+    assert!(
+        comments
+            .insert(Comment {
+                id: 0,
+                text: "Third".into(),
+            })
+            .unwrap()
+    );
+    assert_eq!(
+        comments.load_where(()).unwrap(),
+        vec![
+            Comment {
+                id: 1,
+                text: "First!".into(),
+            },
+            Comment {
+                id: 3,
+                text: "Third".into(),
+            }
+        ]
+    );
+}
+
+#[test]
+fn test_insert_returning() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Ticket {
+        #[silo(primary)]
+        #[silo(autoincrement)]
+        id: i64,
+        #[silo(unique)]
+        title: String,
+        #[silo(skip_insert)]
+        #[silo(default = "open")]
+        status: String,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let tickets = db.load::<Ticket>().unwrap();
+    let ticket = tickets
+        .insert_returning(Ticket {
+            id: 0,
+            title: "Crash on start".into(),
+            status: String::new(),
+        })
+        .unwrap();
+    assert_eq!(
+        ticket,
+        Some(Ticket {
+            id: 1,
+            title: "Crash on start".into(),
+            status: "open".into(),
+        })
+    );
+    assert_eq!(
+        tickets
+            .insert_returning(Ticket {
+                id: 0,
+                title: "Typo".into(),
+                status: String::new(),
+            })
+            .unwrap()
+            .map(|t| t.id),
+        Some(2)
+    );
+    assert_eq!(
+        tickets
+            .insert_returning(Ticket {
+                id: 0,
+                title: "Typo".into(),
+                status: String::new(),
+            })
+            .unwrap(),
+        None
+    );
+    assert_eq!(tickets.load_where(()).unwrap().len(), 2);
+}
+
+#[test]
+fn test_unit_enum_columns() {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, ToColumns)]
+    #[silo(repr_int)]
+    enum Fruit {
+        Apple,
+        Banana,
+        Cherry,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, ToColumns)]
+    enum Size {
+        Small,
+        Large,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Basket {
+        #[silo(primary)]
+        id: u32,
+        fruit: Fruit,
+        size: Option<Size>,
+    }
+
+    let columns = Basket::columns(None, false, false);
+    assert_eq!(columns[1].r#type.as_sql(), "INTEGER");
+    assert_eq!(columns[2].r#type.as_sql(), "TEXT");
+
+    let db = Database::create_in_memory().unwrap();
+    let baskets = db.load::<Basket>().unwrap();
+    let banana = Basket {
+        id: 1,
+        fruit: Fruit::Banana,
+        size: Some(Size::Large),
+    };
+    let cherry = Basket {
+        id: 2,
+        fruit: Fruit::Cherry,
+        size: None,
+    };
+    baskets.insert(banana.clone()).unwrap();
+    baskets.insert(cherry.clone()).unwrap();
+
+    let (fruit, size): (i64, String) = db
+        .connection
+        .query_row("SELECT fruit, size FROM Basket WHERE id = 1", (), |r| {
+            Ok((r.get(0)?, r.get(1)?))
+        })
+        .unwrap();
+    assert_eq!((fruit, size.as_str()), (1, "Large"));
+
+    assert_eq!(
+        baskets.load_where(()).unwrap(),
+        vec![banana.clone(), cherry.clone()]
+    );
+    assert_eq!(
+        baskets
+            .load_where(BasketFilter {
+                fruit: Fruit::Banana.convert_to_equals_filter(),
+                ..Default::default()
+            })
+            .unwrap(),
+        vec![banana.clone()]
+    );
+    assert_eq!(
+        baskets
+            .load_where(BasketFilter {
+                fruit: crate::filter::InFilterable::convert_to_in_filter(vec![
+                    Fruit::Apple,
+                    Fruit::Cherry
+                ]),
+                ..Default::default()
+            })
+            .unwrap(),
+        vec![cherry.clone()]
+    );
+
+    db.connection
+        .execute("UPDATE Basket SET fruit = 7 WHERE id = 2", ())
+        .unwrap();
+    assert!(matches!(
+        baskets.load_where(2),
+        Err(silo::Error::IncompleteRow(_))
+    ));
+}
+
+#[test]
+fn test_filter_all_and_none() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Session {
+        #[silo(primary)]
+        id: u32,
+        is_expired: bool,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let sessions = db.load::<Session>().unwrap();
+    for id in 0..4 {
+        sessions
+            .insert(Session {
+                id,
+                is_expired: id % 2 == 0,
+            })
+            .unwrap();
+    }
+
+    let only_expired = false;
+    let filter = if only_expired {
+        SessionFilter {
+            is_expired: true.convert_to_equals_filter(),
+            ..Default::default()
+        }
+    } else {
+        SessionFilter::none()
+    };
+    assert_eq!(sessions.delete(filter).unwrap(), 0);
+    assert!(!sessions.exists(SessionFilter::none()).unwrap());
+    assert_eq!(sessions.load_where(SessionFilter::all()).unwrap().len(), 4);
+    assert_eq!(
+        sessions
+            .load_where(SessionFilter::none().or(3))
+            .unwrap()
+            .into_iter()
+            .map(|s| s.id)
+            .collect::<Vec<_>>(),
+        vec![3]
+    );
+    assert_eq!(sessions.delete(SessionFilter::all()).unwrap(), 4);
+}
+
+#[test]
+fn test_strict_table() {
+    #[derive(Debug, Clone, PartialEq, ToTable)]
+    #[silo(strict)]
+    struct Measurement {
+        #[silo(primary)]
+        id: u32,
+        value: f64,
+        unit: Option<String>,
+        #[silo(default = 0)]
+        samples: u32,
+    }
+
+    #[derive(Debug, Clone, PartialEq, ToTable)]
+    struct LooseMeasurement {
+        #[silo(primary)]
+        id: u32,
+        value: f64,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let measurements = db.load::<Measurement>().unwrap();
+    let loose = db.load::<LooseMeasurement>().unwrap();
+    let measurement = Measurement {
+        id: 1,
+        value: 2.5,
+        unit: Some("kg".into()),
+        samples: 3,
+    };
+    measurements.insert(measurement.clone()).unwrap();
+    assert_eq!(measurements.load_where(()).unwrap(), vec![measurement]);
+
+    let sql: String = db
+        .connection
+        .query_row(
+            "SELECT sql FROM sqlite_master WHERE name = 'Measurement'",
+            (),
+            |r| r.get(0),
+        )
+        .unwrap();
+    assert!(sql.ends_with(" STRICT"));
+
+    assert!(
+        db.connection
+            .execute(
+                "INSERT INTO Measurement (id, value, unit, samples) VALUES (2, 'heavy', NULL, 0)",
+                (),
+            )
+            .is_err()
+    );
+    db.connection
+        .execute(
+            "INSERT INTO LooseMeasurement (id, value) VALUES (2, 'heavy')",
+            (),
+        )
+        .unwrap();
+    assert_eq!(measurements.load_where(()).unwrap().len(), 1);
+    assert!(loose.load_where(2).is_err());
+}
+
+#[test]
+fn test_generic_order_builder() {
+    let order: GenericOrder = GenericOrder::by("year")
+        .desc()
+        .nulls_last()
+        .then("title")
+        .asc()
+        .into();
+    let mut sql = String::new();
+    order.to_sql(&mut sql);
+    assert_eq!(sql, " ORDER BY \"year\" DESC NULLS LAST, \"title\" ASC");
+    assert_eq!(
+        order,
+        GenericOrder::default()
+            .add(
+                "year",
+                Ordering {
+                    asc_desc: Some(OrderingAscDesc::Descending),
+                    nulls: Some(OrderingNulls::NullsLast),
+                },
+            )
+            .asc("title")
+    );
+
+    let mut sql = String::new();
+    GenericOrder::by("id").build().to_sql(&mut sql);
+    assert_eq!(sql, " ORDER BY \"id\"");
+
+    let mut sql = String::new();
+    GenericOrder::by("say \"hi\"").build().to_sql(&mut sql);
+    assert_eq!(sql, r#" ORDER BY "say ""hi""""#);
+}
+
+#[test]
+fn test_not_null_columns() {
+    #[derive(Debug, Clone, PartialEq, ToTable)]
+    struct Book {
+        #[silo(primary)]
+        id: u32,
+        title: String,
+        subtitle: Option<String>,
+        rating: f64,
+        #[silo(default = 1)]
+        edition: u32,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let books = db.load::<Book>().unwrap();
+    let columns = Book::columns(None, false, false);
+    let not_null: Vec<_> = columns
+        .iter()
+        .filter(|c| c.r#type.is_not_null())
+        .map(|c| c.name.as_ref())
+        .collect();
+    assert_eq!(not_null, ["id", "title", "edition"]);
+
+    let insert = |sql: &str| db.connection.execute(sql, ());
+    assert!(
+        insert("INSERT INTO Book (id, title, subtitle, rating) VALUES (1, NULL, NULL, 1.0)")
+            .is_err()
+    );
+    assert!(insert("INSERT INTO Book (id, title, subtitle, rating, edition) VALUES (1, 'Dune', NULL, 1.0, NULL)").is_err());
+    // Optional fields and floats, which store NaN as NULL, stay nullable.
+    insert("INSERT INTO Book (id, title, subtitle, rating) VALUES (1, 'Dune', NULL, NULL)")
+        .unwrap();
+    books
+        .insert(Book {
+            id: 2,
+            title: "Emma".into(),
+            subtitle: None,
+            rating: 4.5,
+            edition: 2,
+        })
+        .unwrap();
+    assert_eq!(books.load_where(2).unwrap()[0].edition, 2);
+    assert!(books.exists(1).unwrap());
+}
+
+#[test]
+fn test_checkpoint() {
+    #[derive(Debug, Clone, Default, ToTable)]
+    struct Note {
+        text: String,
+    }
+
+    let dir = std::env::temp_dir().join(format!("silo-test-checkpoint-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("checkpoint.db");
+    let wal = dir.join("checkpoint.db-wal");
+    let db = Database::open_with_pragmas(
+        &path,
+        Pragmas {
+            wal: true,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    let notes = db.load::<Note>().unwrap();
+    notes.insert(Note::default()).unwrap();
+    assert!(std::fs::metadata(&wal).unwrap().len() > 0);
+
+    assert!(db.checkpoint(CheckpointMode::Passive).unwrap());
+    assert!(db.checkpoint(CheckpointMode::Full).unwrap());
+    assert!(db.checkpoint(CheckpointMode::Truncate).unwrap());
+    assert_eq!(std::fs::metadata(&wal).unwrap().len(), 0);
+    assert_eq!(notes.load_where(()).unwrap().len(), 1);
+    drop(db);
+
+    let db = Database::create_in_memory().unwrap();
+    assert!(db.checkpoint(CheckpointMode::Truncate).unwrap());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_nested_in_filter() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToColumns)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Test {
+        #[silo(primary)]
+        id: u32,
+        value1: Point,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let tests = db.load::<Test>().unwrap();
+    for id in 0..6 {
+        tests
+            .insert(Test {
+                id,
+                value1: Point {
+                    x: id as i32,
+                    y: -(id as i32),
+                },
+            })
+            .unwrap();
+    }
+    let load_ids = |filter: TestFilter| {
+        tests
+            .load_where(filter)
+            .unwrap()
+            .into_iter()
+            .map(|t| t.id)
+            .collect::<Vec<_>>()
+    };
+
+    let filter = TestFilter {
+        value1: PointFilter {
+            x: FieldFilter::is_one_of([1, 2, 3]),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    assert_eq!(load_ids(filter.clone()), vec![1, 2, 3]);
+
+    assert_eq!(
+        load_ids(TestFilter {
+            value1: PointFilter::default()
+                .x_is_one_of([1, 2, 3])
+                .y_is_one_of([-2, -3, -4]),
+            ..Default::default()
+        }),
+        vec![2, 3]
+    );
+    assert_eq!(
+        load_ids(TestFilter::default().id_is_one_of([0u32, 5])),
+        vec![0, 5]
+    );
+
+    db.set_variable_limit(2).unwrap();
+    assert_eq!(load_ids(filter), vec![1, 2, 3]);
+}
+
+#[test]
+fn test_load_as() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Coord {
+        #[silo(primary)]
+        id: u32,
+        x: i32,
+        y: i32,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    db.connection
+        .execute_batch(
+            "CREATE TABLE legacy_coords (\"id\" INTEGER PRIMARY KEY, \"x\" INTEGER, \"y\" INTEGER);
+            INSERT INTO legacy_coords VALUES (1, 10, 20), (2, 30, 40);",
+        )
+        .unwrap();
+    let coords = db.load_as::<Coord>("legacy_coords").unwrap();
+    assert_eq!(coords.name(), "legacy_coords");
+    assert_eq!(
+        coords
+            .load_where(CoordFilter::default().x_is_not(FieldFilter::equals(10)))
+            .unwrap(),
+        vec![Coord {
+            id: 2,
+            x: 30,
+            y: 40
+        }]
+    );
+
+    coords
+        .insert(Coord {
+            id: 3,
+            x: 50,
+            y: 60,
+        })
+        .unwrap();
+    coords
+        .update(
+            CoordFilter::default().id_is_one_of([1u32]),
+            PartialCoord {
+                y: Some(21),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        coords
+            .delete(CoordFilter::default().id_is_one_of([2u32]))
+            .unwrap(),
+        1
+    );
+    assert_eq!(
+        coords
+            .project::<(i32, i32)>([column_name_of!(Coord, x), column_name_of!(Coord, y)], ())
+            .unwrap(),
+        vec![(10, 21), (50, 60)]
+    );
+    assert_eq!(coords.get_many(vec![3u32]).unwrap().len(), 1);
+
+    // The table named after the type is created and used separately.
+    assert!(!db.connection.table_exists(None, "Coord").unwrap());
+    let default_coords = db.load::<Coord>().unwrap();
+    assert!(default_coords.load_where(()).unwrap().is_empty());
+    assert_eq!(coords.load_where(()).unwrap().len(), 2);
+}
+
+#[test]
+fn test_ip_addr_columns() {
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Host {
+        #[silo(primary)]
+        id: u32,
+        address: IpAddr,
+        v4: Option<Ipv4Addr>,
+        v6: Ipv6Addr,
+    }
+
+    let first = Host {
+        id: 1,
+        address: IpAddr::V4(Ipv4Addr::new(192, 168, 0, 1)),
+        v4: Some(Ipv4Addr::LOCALHOST),
+        v6: Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1),
+    };
+    let second = Host {
+        id: 2,
+        address: IpAddr::V6(Ipv6Addr::LOCALHOST),
+        v4: None,
+        v6: Ipv6Addr::UNSPECIFIED,
+    };
+
+    let db = Database::create_in_memory().unwrap();
+    let hosts = db.load::<Host>().unwrap();
+    hosts.insert(first.clone()).unwrap();
+    hosts.insert(second.clone()).unwrap();
+    assert_eq!(
+        hosts.load_where(()).unwrap(),
+        vec![first.clone(), second.clone()]
+    );
+    let stored: String = db
+        .connection
+        .query_row("SELECT v6 FROM Host WHERE id = 1", (), |r| r.get(0))
+        .unwrap();
+    assert_eq!(stored, "2001:db8::1");
+    assert_eq!(
+        hosts
+            .load_where(HostFilter::default().address_is_one_of([IpAddr::V6(Ipv6Addr::LOCALHOST)]))
+            .unwrap(),
+        vec![second]
+    );
+
+    db.connection
+        .execute("UPDATE Host SET address = 'localhost' WHERE id = 1", ())
+        .unwrap();
+    let mut statement = db
+        .connection
+        .prepare("SELECT address FROM Host WHERE id = 1")
+        .unwrap();
+    let address = statement
+        .query_row((), |r| {
+            Ok(
+                <Option<IpAddr> as crate::ExtractFromRow>::try_from_row_simple("address", r)
+                    .unwrap(),
+            )
+        })
+        .unwrap();
+    assert_eq!(address, None);
+    assert!(matches!(
+        hosts.load_where(HostFilter::default().id_is_one_of([1u32])),
+        Err(crate::Error::IncompleteRow(..))
+    ));
+}
+
+#[test]
+fn test_path_columns() {
+    use std::ffi::OsString;
+    use std::path::PathBuf;
+
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Document {
+        #[silo(primary)]
+        path: PathBuf,
+        backup: Option<PathBuf>,
+        title: OsString,
+    }
+
+    let first = Document {
+        path: PathBuf::from("/home/user/notes.md"),
+        backup: Some(PathBuf::from("backups/notes.md.bak")),
+        title: OsString::from("Notes"),
+    };
+    let second = Document {
+        path: PathBuf::from("relative/ünïcode file.txt"),
+        backup: None,
+        title: OsString::from("Ünïcode"),
+    };
+
+    let db = Database::create_in_memory().unwrap();
+    let documents = db.load::<Document>().unwrap();
+    documents.insert(first.clone()).unwrap();
+    documents.insert(second.clone()).unwrap();
+    assert_eq!(
+        documents.load_where(()).unwrap(),
+        vec![first.clone(), second.clone()]
+    );
+    assert_eq!(
+        documents
+            .load_where(PathBuf::from("/home/user/notes.md"))
+            .unwrap(),
+        vec![first]
+    );
+}
+
+#[test]
+fn test_pending_alterations() {
+    #[derive(Debug, Clone, ToTable)]
+    struct Book {
+        #[silo(primary)]
+        isbn: String,
+        title: String,
+    }
+
+    #[derive(Debug, Clone, ToTable)]
+    struct Shelf {
+        label: String,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    db.connection
+        .execute_batch("CREATE TABLE Book (\"isbn\" TEXT PRIMARY KEY, \"author\" TEXT);")
+        .unwrap();
+    assert_eq!(
+        db.pending_alterations::<Book>().unwrap(),
+        vec![
+            SchemaMismatch::MissingColumn {
+                table: "Book",
+                column: "title".into(),
+            },
+            SchemaMismatch::UnexpectedColumn {
+                table: "Book",
+                column: "author".into(),
+            },
+        ]
+    );
+    assert!(!db.schema_matches::<Shelf>().unwrap());
+    // Checking neither creates nor registers the table.
+    assert!(!db.connection.table_exists(None, "Shelf").unwrap());
+    assert!(db.validate_schema().unwrap().is_empty());
+
+    db.load::<Shelf>().unwrap();
+    assert!(db.pending_alterations::<Shelf>().unwrap().is_empty());
+    assert!(db.schema_matches::<Shelf>().unwrap());
+}
+
+#[test]
+fn test_load_checked() {
+    #[derive(Debug, Clone, PartialEq, ToTable)]
+    struct Book {
+        #[silo(primary)]
+        isbn: String,
+        title: String,
+        #[silo(default = 0)]
+        pages: u32,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    db.connection
+        .execute_batch(
+            "CREATE TABLE Book (\"isbn\" TEXT PRIMARY KEY, \"title\" TEXT NOT NULL);
+             INSERT INTO Book VALUES ('978-0', 'Dune');",
+        )
+        .unwrap();
+    // The outdated table is migrated by adding the column with a default.
+    let books = db.load_checked::<Book>().unwrap();
+    assert_eq!(
+        books.load_where(()).unwrap(),
+        vec![Book {
+            isbn: "978-0".into(),
+            title: "Dune".into(),
+            pages: 0,
+        }]
+    );
+
+    // A column without a default cannot be added, so this table stays
+    // mismatched. Plain `load` uses it anyway.
+    let db = Database::create_in_memory().unwrap();
+    db.connection
+        .execute_batch("CREATE TABLE Book (\"isbn\" TEXT PRIMARY KEY, \"pages\" INTEGER);")
+        .unwrap();
+    let Err(Error::SchemaMismatch(mismatches)) = db.load_checked::<Book>() else {
+        panic!("expected a schema mismatch");
+    };
+    assert_eq!(
+        mismatches,
+        vec![SchemaMismatch::MissingColumn {
+            table: "Book",
+            column: "title".into(),
+        }]
+    );
+    assert!(db.load::<Book>().is_ok());
+}
+
+#[test]
+fn test_column_type_affinity() {
+    #[derive(Debug, Clone, PartialEq, ToTable)]
+    struct Product {
+        #[silo(primary)]
+        id: i64,
+        name: String,
+        price: f64,
+        stock: u32,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    db.connection
+        .execute_batch(
+            "CREATE TABLE Product (\"id\" BIGINT PRIMARY KEY, \"name\" VARCHAR(255), \"price\" NUMERIC, \"stock\" NUMERIC);
+            INSERT INTO Product VALUES (1, 'Tea', 3.5, 12);",
+        )
+        .unwrap();
+    let types: Vec<_> = db
+        .introspect("Product")
+        .unwrap()
+        .into_iter()
+        .map(|c| c.r#type)
+        .collect();
+    assert_eq!(
+        types,
+        [
+            crate::SqlColumnType::Integer,
+            crate::SqlColumnType::Text,
+            crate::SqlColumnType::Null,
+            crate::SqlColumnType::Null,
+        ]
+    );
+    assert!(db.pending_alterations::<Product>().unwrap().is_empty());
+
+    let products = db.load::<Product>().unwrap();
+    assert_eq!(
+        products.load_where(()).unwrap(),
+        vec![Product {
+            id: 1,
+            name: "Tea".into(),
+            price: 3.5,
+            stock: 12,
+        }]
+    );
+}
+
+#[test]
+fn test_cow_str_columns() {
+    use std::borrow::Cow;
+
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Setting {
+        #[silo(primary)]
+        key: Cow<'static, str>,
+        value: Cow<'static, str>,
+        description: Option<Cow<'static, str>>,
+    }
+
+    let first = Setting {
+        key: Cow::Borrowed("theme"),
+        value: Cow::Borrowed("dark"),
+        description: Some(Cow::Owned(format!("{} theme", "Color"))),
+    };
+    let second = Setting {
+        key: Cow::Owned("language".into()),
+        value: Cow::Borrowed("en"),
+        description: None,
+    };
+
+    let db = Database::create_in_memory().unwrap();
+    let settings = db.load::<Setting>().unwrap();
+    settings.insert(first.clone()).unwrap();
+    settings.insert(second.clone()).unwrap();
+    assert_eq!(
+        settings.load_where(()).unwrap(),
+        vec![first.clone(), second]
+    );
+    assert_eq!(
+        settings.load_where(Cow::Borrowed("theme")).unwrap(),
+        vec![first]
+    );
+    assert!(matches!(
+        settings.load_where(()).unwrap()[0].key,
+        Cow::Owned(_)
+    ));
+}
+
+#[test]
+fn test_composite_unique() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    #[silo(unique(movie_id, genre_id))]
+    struct MovieWithGenres {
+        movie_id: u32,
+        genre_id: u32,
+        rank: u32,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let movies = db.load::<MovieWithGenres>().unwrap();
+    let row = |movie_id, genre_id, rank| MovieWithGenres {
+        movie_id,
+        genre_id,
+        rank,
+    };
+    assert!(movies.insert(row(1, 1, 0)).unwrap());
+    assert!(movies.insert(row(1, 2, 0)).unwrap());
+    assert!(movies.insert(row(2, 1, 0)).unwrap());
+    assert!(!movies.insert(row(1, 2, 5)).unwrap());
+    assert_eq!(movies.load_where(()).unwrap().len(), 3);
+
+    assert!(
+        movies
+            .insert_with(row(1, 2, 5), SqlFailureBehavior::Replace)
+            .unwrap()
+    );
+    assert_eq!(
+        movies
+            .load_where(MovieWithGenresFilter::default().movie_id_is_one_of([1u32]))
+            .unwrap(),
+        vec![row(1, 1, 0), row(1, 2, 5)]
+    );
+}
+
+#[test]
+fn test_composite_unique_of_raw_identifiers() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    #[silo(unique(r#type, name))]
+    struct Setting {
+        r#type: String,
+        name: String,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let settings = db.load::<Setting>().unwrap();
+    let row = |r#type: &str, name: &str| Setting {
+        r#type: r#type.into(),
+        name: name.into(),
+    };
+    assert!(settings.insert(row("color", "accent")).unwrap());
+    assert!(settings.insert(row("font", "accent")).unwrap());
+    assert!(!settings.insert(row("color", "accent")).unwrap());
+}
+
+#[test]
+fn test_primary_key_accessor() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Genre {
+        name: String,
+        #[silo(primary)]
+        id: u32,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let genres = db.load::<Genre>().unwrap();
+    let drama = Genre {
+        name: "Drama".into(),
+        id: 18,
+    };
+    assert_eq!(drama.primary_key(), &18);
+    genres.insert(drama.clone()).unwrap();
+    let loaded = genres.load_where(*drama.primary_key()).unwrap();
+    assert_eq!(loaded, vec![drama]);
+}
+
+#[test]
+fn test_load_map() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Genre {
+        #[silo(primary)]
+        id: u32,
+        name: String,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let genres = db.load::<Genre>().unwrap();
+    assert!(genres.load_map().unwrap().is_empty());
+    for (id, name) in [(18, "Drama"), (35, "Comedy"), (27, "Horror")] {
+        genres
+            .insert(Genre {
+                id,
+                name: name.into(),
+            })
+            .unwrap();
+    }
+
+    let map = genres.load_map().unwrap();
+    assert_eq!(map.len(), 3);
+    assert_eq!(map[&35].name, "Comedy");
+    assert!(!map.contains_key(&12));
+}
+
+#[cfg(feature = "time")]
+#[test]
+fn test_time_columns() {
+    use time::macros::{date, datetime, time};
+    use time::{Date, OffsetDateTime, Time};
+
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Shift {
+        #[silo(primary)]
+        id: u32,
+        day: Date,
+        start: Time,
+        confirmed: Option<OffsetDateTime>,
+    }
+
+    let first = Shift {
+        id: 1,
+        day: date!(2024 - 02 - 29),
+        start: time!(09:30:00.25),
+        confirmed: Some(datetime!(2024-02-28 18:15:30.123456789 +02:00)),
+    };
+    let second = Shift {
+        id: 2,
+        day: date!(2024 - 03 - 01),
+        start: time!(14:00),
+        confirmed: Some(datetime!(2024-02-29 00:00 UTC)),
+    };
+    let third = Shift {
+        id: 3,
+        day: date!(2024 - 03 - 01),
+        start: time!(23:59:59.999999999),
+        confirmed: None,
+    };
+
+    let db = Database::create_in_memory().unwrap();
+    let shifts = db.load::<Shift>().unwrap();
+    for shift in [&first, &second, &third] {
+        shifts.insert(shift.clone()).unwrap();
+    }
+    assert_eq!(
+        shifts.load_where(()).unwrap(),
+        vec![first.clone(), second.clone(), third.clone()]
+    );
+
+    let load_ids = |filter: ShiftFilter| {
+        shifts
+            .load_where(filter)
+            .unwrap()
+            .into_iter()
+            .map(|s| s.id)
+            .collect::<Vec<_>>()
+    };
+    for shift in [&first, &second, &third] {
+        assert_eq!(
+            load_ids(ShiftFilter {
+                start: shift.start.convert_to_equals_filter(),
+                ..Default::default()
+            }),
+            vec![shift.id]
+        );
+    }
+    assert_eq!(
+        load_ids(ShiftFilter {
+            day: date!(2024 - 03 - 01).convert_to_equals_filter(),
+            ..Default::default()
+        }),
+        vec![2, 3]
+    );
+    assert_eq!(
+        load_ids(ShiftFilter {
+            confirmed: OptionalFilter::IsSomeAnd(
+                first.confirmed.unwrap().convert_to_equals_filter()
+            ),
+            ..Default::default()
+        }),
+        vec![1]
+    );
+    assert_eq!(
+        load_ids(ShiftFilter {
+            confirmed: OptionalFilter::IsSomeAnd(
+                second.confirmed.unwrap().convert_to_equals_filter()
+            ),
+            ..Default::default()
+        }),
+        vec![2]
+    );
+}
+
+#[test]
+fn test_keep_nan() {
+    #[derive(Debug, Clone, ToTable)]
+    struct Measurement {
+        #[silo(primary)]
+        id: u32,
+        value: Option<KeepNan<f64>>,
+        error: KeepNan<f32>,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let measurements = db.load::<Measurement>().unwrap();
+    for (id, value, error) in [
+        (1, Some(f64::NAN), f32::NAN),
+        (2, None, 0.5),
+        (3, Some(2.5), 0.25),
+        (4, Some(-1.0), 1.0),
+    ] {
+        measurements
+            .insert(Measurement {
+                id,
+                value: value.map(KeepNan),
+                error: KeepNan(error),
+            })
+            .unwrap();
+    }
+
+    let loaded = measurements.load_where(()).unwrap();
+    assert!(loaded[0].value.unwrap().0.is_nan());
+    assert!(loaded[0].error.0.is_nan());
+    assert_eq!(loaded[1].value, None);
+    assert_eq!(loaded[2].value, Some(KeepNan(2.5)));
+    assert_eq!(loaded[3].error, KeepNan(1.0));
+
+    let load_ids = |filter: MeasurementFilter| {
+        measurements
+            .load_where(filter)
+            .unwrap()
+            .into_iter()
+            .map(|m| m.id)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        load_ids(MeasurementFilter {
+            value: OptionalFilter::IsSomeAnd(FieldFilter::<KeepNan<f64>>::is_nan()),
+            ..Default::default()
+        }),
+        vec![1]
+    );
+    assert_eq!(
+        load_ids(MeasurementFilter {
+            value: OptionalFilter::IsSomeAnd(FieldFilter::greater_than(KeepNan(0.0))),
+            ..Default::default()
+        }),
+        vec![3]
+    );
+    assert_eq!(
+        load_ids(MeasurementFilter {
+            error: FieldFilter::is_one_of([KeepNan(f32::NAN), KeepNan(1.0)]),
+            ..Default::default()
+        }),
+        vec![1, 4]
+    );
+    assert_eq!(
+        load_ids(MeasurementFilter {
+            error: FieldFilter::less_than(KeepNan(f32::NAN)),
+            ..Default::default()
+        }),
+        Vec::<u32>::new()
+    );
+}
+
+#[test]
+fn test_bool_check() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Task {
+        #[silo(primary)]
+        id: u32,
+        done: bool,
+        archived: Option<bool>,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let tasks = db.load::<Task>().unwrap();
+    tasks
+        .insert(Task {
+            id: 1,
+            done: true,
+            archived: Some(false),
+        })
+        .unwrap();
+    let sql: String = db
+        .connection
+        .query_row(
+            "SELECT sql FROM sqlite_master WHERE name = 'Task'",
+            (),
+            |r| r.get(0),
+        )
+        .unwrap();
+    assert!(sql.contains("\"done\" INTEGER NOT NULL CHECK (\"done\" IN (0, 1))"));
+    assert!(
+        db.connection
+            .execute("INSERT INTO Task VALUES (2, 2, NULL)", ())
+            .is_err()
+    );
+
+    // Tables without the constraint may hold other values, which are not
+    // read as a bool.
+    db.connection
+        .execute_batch(
+            "CREATE TABLE OldTask (id INTEGER PRIMARY KEY, done INTEGER NOT NULL, archived INTEGER);
+             INSERT INTO OldTask VALUES (1, 1, 2);",
+        )
+        .unwrap();
+    let old_tasks = db.load_as::<Task>("OldTask").unwrap();
+    assert_eq!(
+        old_tasks.load_where(()).unwrap(),
+        vec![Task {
+            id: 1,
+            done: true,
+            archived: None,
+        }]
+    );
+    db.connection
+        .execute("INSERT INTO OldTask VALUES (2, 2, 0)", ())
+        .unwrap();
+    assert!(matches!(
+        old_tasks.load_where(()),
+        Err(Error::IncompleteRow(_))
+    ));
+}
+
+#[test]
+fn test_soft_delete() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    #[silo(soft_delete)]
+    struct Note {
+        #[silo(primary)]
+        id: u32,
+        text: String,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    db.connection
+        .execute_batch(
+            "CREATE TABLE Note (\"id\" INTEGER PRIMARY KEY, \"text\" TEXT NOT NULL);
+             INSERT INTO Note VALUES (1, 'milk'), (2, 'eggs');",
+        )
+        .unwrap();
+    // Loading an existing table adds the column.
+    let notes = db.load_checked::<Note>().unwrap();
+    notes
+        .insert(Note {
+            id: 3,
+            text: "bread".into(),
+        })
+        .unwrap();
+
+    assert_eq!(notes.delete(2).unwrap(), 1);
+    let ids = |filter: NoteFilter| -> Vec<u32> {
+        notes
+            .load_where(filter)
+            .unwrap()
+            .into_iter()
+            .map(|n| n.id)
+            .collect()
+    };
+    assert_eq!(ids(NoteFilter::default()), vec![1, 3]);
+    assert_eq!(ids(NoteFilter::default().include_deleted()), vec![1, 2, 3]);
+    assert!(!notes.exists(2).unwrap());
+    assert!(notes.exists(NoteFilter::from(2).include_deleted()).unwrap());
+    assert_eq!(notes.count_distinct("text").unwrap(), 2);
+
+    // Deleted rows are neither deleted again nor updated.
+    assert_eq!(notes.delete(2).unwrap(), 0);
+    assert_eq!(
+        notes
+            .update(
+                NoteFilter::default(),
+                PartialNote {
+                    text: Some("gone".into()),
+                    ..Default::default()
+                },
+            )
+            .unwrap(),
+        2
+    );
+    let deleted = notes
+        .load_where(NoteFilter::from(2).include_deleted())
+        .unwrap();
+    assert_eq!(deleted[0].text, "eggs");
+    let deleted_at: Option<String> = db
+        .connection
+        .query_row(
+            &format!("SELECT \"{SOFT_DELETE_COLUMN}\" FROM Note WHERE id = 2"),
+            (),
+            |r| r.get(0),
+        )
+        .unwrap();
+    assert!(deleted_at.is_some());
+
+    assert_eq!(notes.clear().unwrap(), 2);
+    assert!(notes.load_where(()).unwrap().is_empty());
+    assert_eq!(ids(NoteFilter::default().include_deleted()).len(), 3);
+}
+
+#[test]
+fn test_soft_delete_alternatives_and_nested_filters() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    #[silo(soft_delete)]
+    struct Note {
+        #[silo(primary)]
+        id: u32,
+        text: String,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let notes = db.load::<Note>().unwrap();
+    for (id, text) in [(1, "milk"), (2, "eggs"), (3, "bread")] {
+        notes
+            .insert(Note {
+                id,
+                text: text.into(),
+            })
+            .unwrap();
+    }
+    assert_eq!(notes.delete(NoteFilter::from(2).or(3)).unwrap(), 2);
+    let ids = |filter: NoteFilter| -> Vec<u32> {
+        notes
+            .load_where(filter)
+            .unwrap()
+            .into_iter()
+            .map(|n| n.id)
+            .collect()
+    };
+    assert_eq!(ids(NoteFilter::from(1).or(2)), vec![1]);
+    assert_eq!(
+        ids(NoteFilter::from(1).or(2).or(3).include_deleted()),
+        vec![1, 2, 3]
+    );
+
+    // As the filter of a nested field, the table it is part of has no
+    // deleted_at column.
+    let mut sql = String::from("WHERE ");
+    silo::filter::Filter::to_sql(&NoteFilter::from(2), &mut sql, Some("note"));
+    assert_eq!(sql, "WHERE note_id = 2");
+}
+
+#[test]
+fn test_generate_insert_struct() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    #[silo(generate_insert_struct)]
+    struct Genre {
+        #[silo(primary)]
+        id: i64,
+        name: String,
+        #[silo(default = 0)]
+        #[silo(skip_insert)]
+        movie_count: u32,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let genres = db.load::<Genre>().unwrap();
+    assert!(
+        genres
+            .insert_new(NewGenre {
+                name: "Drama".into(),
+            })
+            .unwrap()
+    );
+    assert!(
+        genres
+            .insert_new(NewGenre {
+                name: "Comedy".into(),
+            })
+            .unwrap()
+    );
+    assert_eq!(
+        genres.load_where(()).unwrap(),
+        vec![
+            Genre {
+                id: 1,
+                name: "Drama".into(),
+                movie_count: 0,
+            },
+            Genre {
+                id: 2,
+                name: "Comedy".into(),
+                movie_count: 0,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_update_each() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Genre {
+        #[silo(primary)]
+        id: u32,
+        movie_count: u32,
+        #[silo(skip_select)]
+        note: String,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let genres = db.load::<Genre>().unwrap();
+    for id in 1..=4 {
+        genres
+            .insert(Genre {
+                id,
+                movie_count: id * 10,
+                note: format!("note {id}"),
+            })
+            .unwrap();
+    }
+
+    let updated = genres
+        .update_each((), |g| {
+            g.movie_count += 1;
+            g.id % 2 == 0
+        })
+        .unwrap();
+    assert_eq!(updated, 2);
+
+    let counts: Vec<_> = genres
+        .load_where(())
+        .unwrap()
+        .into_iter()
+        .map(|g| g.movie_count)
+        .collect();
+    assert_eq!(counts, [10, 21, 30, 41]);
+    let note: String = db
+        .connection
+        .query_row("SELECT note FROM Genre WHERE id = 2", (), |r| r.get(0))
+        .unwrap();
+    assert_eq!(note, "note 2");
+}
+
+#[test]
+fn test_generic_columns() {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, ToColumns)]
+    #[silo(transparent)]
+    struct Id<T>(T);
+
+    #[derive(Debug, Clone, PartialEq, ToColumns)]
+    struct Measured<T> {
+        value: T,
+        unit: String,
+    }
+
+    #[derive(Debug, Clone, PartialEq, ToTable)]
+    struct Reading {
+        #[silo(primary)]
+        id: Id<u32>,
+        temperature: Measured<f64>,
+        samples: Measured<u32>,
+    }
+
+    let column_names: Vec<_> = Reading::columns(None, false, false)
+        .into_iter()
+        .map(|c| c.name)
+        .collect();
+    assert_eq!(
+        column_names,
+        [
+            "id",
+            "temperature_value",
+            "temperature_unit",
+            "samples_value",
+            "samples_unit"
+        ]
+    );
+
+    let db = Database::create_in_memory().unwrap();
+    let readings = db.load::<Reading>().unwrap();
+    let cold = Reading {
+        id: Id(1),
+        temperature: Measured {
+            value: -4.5,
+            unit: "°C".into(),
+        },
+        samples: Measured {
+            value: 3,
+            unit: "count".into(),
+        },
+    };
+    let warm = Reading {
+        id: Id(2),
+        temperature: Measured {
+            value: 21.0,
+            unit: "°C".into(),
+        },
+        samples: Measured {
+            value: 12,
+            unit: "count".into(),
+        },
+    };
+    readings.insert(cold.clone()).unwrap();
+    readings.insert(warm.clone()).unwrap();
+
+    assert_eq!(readings.load_where(Id(2)).unwrap(), vec![warm.clone()]);
+    let loaded = readings
+        .load_where(ReadingFilter {
+            samples: MeasuredFilter {
+                value: FieldFilter::less_than(10u32),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(loaded, vec![cold.clone()]);
+
+    readings
+        .update(
+            Id(1),
+            PartialReading {
+                samples: PartialMeasured {
+                    value: Some(4),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(readings.load_where(Id(1)).unwrap()[0].samples.value, 4);
+}
+
+#[test]
+fn test_load_attached() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Genre {
+        #[silo(primary)]
+        id: u32,
+        #[silo(index)]
+        name: String,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    db.attach(":memory:", "reference").unwrap();
+    let reference = db.load_attached::<Genre>("reference").unwrap();
+    let local = db.load::<Genre>().unwrap();
+    let drama = Genre {
+        id: 18,
+        name: "Drama".into(),
+    };
+    let comedy = Genre {
+        id: 35,
+        name: "Comedy".into(),
+    };
+    reference.insert(drama.clone()).unwrap();
+    local.insert(comedy.clone()).unwrap();
+
+    assert_eq!(reference.load_where(()).unwrap(), vec![drama.clone()]);
+    assert_eq!(local.load_where(()).unwrap(), vec![comedy.clone()]);
+    assert_eq!(reference.find_one(18).unwrap(), Some(drama));
+    assert_eq!(reference.delete(18).unwrap(), 1);
+    assert!(reference.load_where(()).unwrap().is_empty());
+
+    let index_count: u32 = db
+        .connection
+        .query_row(
+            "SELECT COUNT(*) FROM reference.sqlite_master WHERE type = 'index' AND name = 'idx_Genre_name'",
+            (),
+            |r| r.get(0),
+        )
+        .unwrap();
+    assert_eq!(index_count, 1);
+
+    assert_eq!(reference.name(), "Genre");
+    assert_eq!(reference.schema(), Some("reference"));
+    assert_eq!(local.schema(), None);
+    let attached = TableName {
+        schema: Some("reference"),
+        name: "Genre",
+    };
+    assert_eq!(attached.to_string(), r#""reference"."Genre""#);
+    assert_eq!(db.introspect(attached).unwrap().len(), 2);
+
+    // Quotes in the alias are escaped, so any alias works.
+    db.attach(":memory:", r#"my "archive""#).unwrap();
+    let archive = db.load_attached::<Genre>(r#"my "archive""#).unwrap();
+    archive.insert(comedy).unwrap();
+    assert_eq!(archive.load_where(()).unwrap().len(), 1);
+    db.drop_table::<Genre>().unwrap();
+    assert_eq!(archive.load_where(()).unwrap().len(), 1);
+}
+
+#[test]
+fn test_optional_nested_columns_round_trip() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToColumns)]
+    struct Country {
+        code: String,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, ToColumns)]
+    struct Address {
+        city: String,
+        country: Country,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Person {
+        #[silo(primary)]
+        id: u32,
+        address: Option<Address>,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let people = db.load::<Person>().unwrap();
+    let homeless = Person {
+        id: 1,
+        address: None,
+    };
+    let resident = Person {
+        id: 2,
+        address: Some(Address {
+            city: "Berlin".into(),
+            country: Country { code: "DE".into() },
+        }),
+    };
+    people.insert(homeless.clone()).unwrap();
+    people.insert(resident.clone()).unwrap();
+
+    let nulls: (Option<String>, Option<String>) = db
+        .connection
+        .query_row(
+            "SELECT address_city, address_country_code FROM Person WHERE id = 1",
+            (),
+            |r| Ok((r.get(0)?, r.get(1)?)),
+        )
+        .unwrap();
+    assert_eq!(nulls, (None, None));
+    assert_eq!(people.load_where(()).unwrap(), vec![homeless, resident]);
+
+    people
+        .update(
+            2,
+            PartialPerson {
+                address: Some(None),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(people.load_where(2).unwrap()[0].address, None);
+}
+
+#[test]
+fn test_check_attribute() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    struct Person {
+        #[silo(primary)]
+        id: u32,
+        #[silo(check = "age >= 0")]
+        age: i32,
+        #[silo(check = "retired = 0 OR age >= 60")]
+        retired: bool,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let people = db.load::<Person>().unwrap();
+    people
+        .insert(Person {
+            id: 1,
+            age: 30,
+            retired: false,
+        })
+        .unwrap();
+    // Like other constraint violations, the row is not inserted.
+    assert!(
+        !people
+            .insert(Person {
+                id: 2,
+                age: -1,
+                retired: false,
+            })
+            .unwrap()
+    );
+    assert!(
+        !people
+            .insert(Person {
+                id: 3,
+                age: 40,
+                retired: true,
+            })
+            .unwrap()
+    );
+    let result = db
+        .connection
+        .execute("INSERT INTO Person VALUES (4, -5, 0)", ());
+    assert!(matches!(
+        result,
+        Err(rusqlite::Error::SqliteFailure(
+            rusqlite::ffi::Error {
+                code: rusqlite::ErrorCode::ConstraintViolation,
+                ..
+            },
+            _
+        ))
+    ));
+
+    let sql: String = db
+        .connection
+        .query_row(
+            "SELECT sql FROM sqlite_master WHERE name = 'Person'",
+            (),
+            |r| r.get(0),
+        )
+        .unwrap();
+    assert!(sql.contains("CHECK ((\"retired\" IN (0, 1)) AND (retired = 0 OR age >= 60))"));
+    assert_eq!(people.load_where(()).unwrap().len(), 1);
+}
+
+#[test]
+fn test_replace_conflict_columns() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    #[silo(replace(email))]
+    struct User {
+        #[silo(primary)]
+        id: u32,
+        #[silo(unique)]
+        email: String,
+        #[silo(unique)]
+        name: String,
+        karma: i64,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let users = db.load::<User>().unwrap();
+    let user = |id, email: &str, name: &str, karma| User {
+        id,
+        email: email.into(),
+        name: name.into(),
+        karma,
+    };
+    assert!(users.insert(user(1, "a@example.com", "alice", 1)).unwrap());
+    assert!(users.insert(user(2, "b@example.com", "bob", 2)).unwrap());
+
+    // A conflict on the email replaces the values of the existing row.
+    assert!(users.insert(user(1, "a@example.com", "alicia", 5)).unwrap());
+    // A conflict on the name is not replaced, so bob is kept.
+    assert!(!users.insert(user(3, "c@example.com", "bob", 7)).unwrap());
+    assert_eq!(
+        users.load_where(()).unwrap(),
+        vec![
+            user(1, "a@example.com", "alicia", 5),
+            user(2, "b@example.com", "bob", 2)
+        ]
+    );
+
+    assert_eq!(
+        users
+            .insert_returning_id(user(2, "b@example.com", "bobby", 3))
+            .unwrap(),
+        Some(2)
+    );
+    assert_eq!(users.load_where(()).unwrap().len(), 2);
+}
+
+#[test]
+fn test_insert_new_replaces_on_conflict_columns() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    #[silo(generate_insert_struct)]
+    #[silo(replace(email))]
+    struct User {
+        #[silo(primary)]
+        id: i64,
+        #[silo(unique)]
+        email: String,
+        #[silo(unique)]
+        name: String,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let users = db.load::<User>().unwrap();
+    let user = |email: &str, name: &str| NewUser {
+        email: email.into(),
+        name: name.into(),
+    };
+    assert!(users.insert_new(user("a@example.com", "alice")).unwrap());
+    assert!(users.insert_new(user("b@example.com", "bob")).unwrap());
+
+    // A conflict on the email updates the existing row.
+    assert!(users.insert_new(user("a@example.com", "alicia")).unwrap());
+    // A conflict on the name is not replaced, so bob is kept.
+    assert!(!users.insert_new(user("c@example.com", "bob")).unwrap());
+    assert_eq!(
+        users.load_where(()).unwrap(),
+        vec![
+            User {
+                id: 1,
+                email: "a@example.com".into(),
+                name: "alicia".into(),
+            },
+            User {
+                id: 2,
+                email: "b@example.com".into(),
+                name: "bob".into(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_replace_conflict_columns_of_unique_constraint() {
+    #[derive(Debug, Clone, PartialEq, Eq, ToTable)]
+    #[silo(unique(r#type, name))]
+    #[silo(replace(name, r#type))]
+    struct Setting {
+        r#type: String,
+        name: String,
+        value: String,
+    }
+
+    let db = Database::create_in_memory().unwrap();
+    let settings = db.load::<Setting>().unwrap();
+    let row = |r#type: &str, name: &str, value: &str| Setting {
+        r#type: r#type.into(),
+        name: name.into(),
+        value: value.into(),
+    };
+    assert!(settings.insert(row("color", "accent", "red")).unwrap());
+    assert!(settings.insert(row("font", "accent", "serif")).unwrap());
+    assert!(settings.insert(row("color", "accent", "blue")).unwrap());
+    assert_eq!(
+        settings.load_where(()).unwrap(),
+        vec![
+            row("color", "accent", "blue"),
+            row("font", "accent", "serif")
+        ]
+    );
+}
+
+#[test]
+fn test_derive_errors() {
+    // Each file in tests/ui must fail to compile with the message and span in
+    // the .stderr file next to it. TRYBUILD=overwrite updates them.
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+    // The regex filters are only generated with the feature.
+    #[cfg(not(feature = "regex"))]
+    cases.compile_fail("tests/ui-no-regex/*.rs");
+}
@@ -0,0 +1,12 @@
+use silo::derive::ToTable;
+
+#[derive(ToTable)]
+struct Movie {
+    #[silo(primary)]
+    id: u32,
+    title: String,
+}
+
+fn main() {
+    let _ = MovieFilter::default().title_matches("^The");
+}
@@ -0,0 +1,10 @@
+use silo::derive::ToTable;
+
+#[derive(ToTable)]
+#[silo(unique(nmae))]
+enum Shape {
+    Circle { name: String, radius: u32 },
+    Square { side: u32 },
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use silo::derive::{ToColumns, ToTable};
+
+#[derive(ToColumns)]
+struct Position {
+    x: i32,
+    y: i32,
+}
+
+#[derive(ToTable)]
+#[silo(unique(board, position))]
+struct Piece {
+    board: u32,
+    position: Position,
+}
+
+fn main() {}
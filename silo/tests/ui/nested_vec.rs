@@ -0,0 +1,10 @@
+use silo::derive::ToTable;
+
+#[derive(ToTable)]
+struct Matrix {
+    #[silo(primary)]
+    id: u32,
+    rows: Vec<Vec<f64>>,
+}
+
+fn main() {}
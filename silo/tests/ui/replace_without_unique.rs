@@ -0,0 +1,13 @@
+use silo::derive::ToTable;
+
+#[derive(ToTable)]
+#[silo(unique(email, name))]
+#[silo(replace(email))]
+struct User {
+    #[silo(primary)]
+    id: u32,
+    email: String,
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use silo::derive::ToTable;
+
+#[derive(ToTable)]
+struct Post {
+    #[silo(primary)]
+    id: u32,
+    #[silo(skip_insert)]
+    views: u32,
+    #[silo(skip_insert)]
+    editor: Option<String>,
+}
+
+fn main() {}